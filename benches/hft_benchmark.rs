@@ -1,214 +1,276 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use benchmark_async_vs_sync::environment::Environment;
+use benchmark_async_vs_sync::interner::{KeyStrategy, OrderId, SymbolTable};
+use benchmark_async_vs_sync::latency::{self, LatencyRecorder, LatencySummary, Operation};
+use benchmark_async_vs_sync::open_loop::{self, RunStats, Schedule};
+use benchmark_async_vs_sync::order_book::{FloatPrice, OrderBook, OrderBooks, PriceLevelKey};
+use benchmark_async_vs_sync::orders::{CompactOrder, Order, OrderStatus};
+use benchmark_async_vs_sync::price::{Price, SymbolSpecs};
+use benchmark_async_vs_sync::scenario_file::{CriterionSettings, PriceLevels, RuntimeSettings, ScenarioFile, ScenarioSpec, WorkloadSpec};
+use benchmark_async_vs_sync::scenarios::{self, Scenario, TaskStrategy};
+use benchmark_async_vs_sync::stop_orders::{StopTriggers, TriggerNotifier};
+use benchmark_async_vs_sync::workload::{self, OrderWorkload, WorkloadGenerator};
+use criterion::measurement::Measurement;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkGroup, Criterion, BenchmarkId};
 use dashmap::DashMap;
 use futures::future::join_all;
 use rand::prelude::*;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 use tokio::runtime::Runtime;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-enum OrderSide {
-    Buy,
-    Sell,
-}
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-enum OrderType {
-    Market,
-    Limit,
-    Stop,
-    StopLimit,
-}
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
-enum OrderStatus {
-    New,
-    PartiallyFilled,
-    Filled,
-    Cancelled,
-    Rejected,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Order {
-    order_id: u64,
-    symbol: String,
-    side: OrderSide,
-    order_type: OrderType,
-    quantity: u64,
-    price: f64,
-    filled_quantity: u64,
-    remaining_quantity: u64,
-    status: OrderStatus,
-    timestamp: u64,
-    client_id: String,
-}
-
-impl Order {
-    fn new(order_id: u64, symbol: String, client_id: String) -> Self {
-        let mut rng = thread_rng();
-        let quantity = rng.gen_range(1..10000);
-        Self {
-            order_id,
-            symbol,
-            side: if rng.gen_bool(0.5) { OrderSide::Buy } else { OrderSide::Sell },
-            order_type: match rng.gen_range(0..4) {
-                0 => OrderType::Market,
-                1 => OrderType::Limit,
-                2 => OrderType::Stop,
-                _ => OrderType::StopLimit,
-            },
-            quantity,
-            price: rng.gen_range(10.0..1000.0),
-            filled_quantity: 0,
-            remaining_quantity: quantity,
-            status: OrderStatus::New,
-            timestamp: rng.gen_range(1600000000..1700000000),
-            client_id,
+fn criterion_dir() -> PathBuf {
+    PathBuf::from(std::env::var("CRITERION_HOME").unwrap_or_else(|_| "target/criterion".to_string()))
+}
+
+// Seed shared by every scenario in this run, recorded for plot_results
+fn workload_seed() -> u64 {
+    static SEED: OnceLock<u64> = OnceLock::new();
+    *SEED.get_or_init(|| {
+        let seed = workload::seed_from_env().unwrap_or_else(|err| panic!("{}", err));
+        if let Err(err) = workload::record_seed(&criterion_dir(), seed) {
+            eprintln!("warning: could not record workload seed: {}", err);
         }
-    }
-    
-    fn update_fill(&mut self, fill_quantity: u64) {
-        let actual_fill = std::cmp::min(fill_quantity, self.remaining_quantity);
-        self.filled_quantity += actual_fill;
-        self.remaining_quantity -= actual_fill;
-        
-        if self.remaining_quantity == 0 {
-            self.status = OrderStatus::Filled;
-        } else {
-            self.status = OrderStatus::PartiallyFilled;
+        println!("Workload seed: {} (set {} to override)", seed, workload::SEED_ENV_VAR);
+        // Recorded once per run alongside the seed
+        if let Err(err) = Environment::capture().record(&criterion_dir()) {
+            eprintln!("warning: could not record environment: {}", err);
         }
-    }
+        seed
+    })
 }
 
-// Sync operations
-fn sync_order_operations(data_size: usize) {
-    let mut order_map: HashMap<String, Order> = HashMap::new();
-    let mut rng = thread_rng();
-    
+// Interned variants: numeric order ids and pre-interned symbols/clients, so
+// no strings are formatted or cloned per operation
+fn sync_interned_order_operations(orders: &[CompactOrder], fills: &[u64]) {
+    let mut order_map: HashMap<OrderId, CompactOrder> = HashMap::new();
+
     // Write operations - Create orders
-    for i in 0..data_size {
-        let order_id = format!("ORD_{}", i);
-        let symbol = format!("STOCK_{}", i % 1000); // Cycle through 1000 symbols
-        let client_id = format!("CLIENT_{}", i % 100); // 100 clients
-        let order = Order::new(i as u64, symbol, client_id);
-        order_map.insert(order_id, order);
+    for order in orders {
+        order_map.insert(order.order_id, *order);
     }
-    
+
     // Read operations - Query orders
-    for i in 0..data_size {
-        let order_id = format!("ORD_{}", i);
-        black_box(order_map.get(&order_id));
+    for order in orders {
+        black_box(order_map.get(&order.order_id));
     }
-    
+
     // Update operations - Fill orders
-    for i in 0..data_size {
-        let order_id = format!("ORD_{}", i);
-        if let Some(order) = order_map.get_mut(&order_id) {
+    for (order, &fill_qty) in orders.iter().zip(fills) {
+        if let Some(order) = order_map.get_mut(&order.order_id) {
             if order.remaining_quantity > 0 {
-                let fill_qty = rng.gen_range(1..=order.remaining_quantity);
                 order.update_fill(fill_qty);
             }
         }
     }
 }
 
-fn sync_concurrent_order_operations(data_size: usize) {
-    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
-    let mut rng = thread_rng();
-    
+fn sync_concurrent_interned_order_operations(orders: &[CompactOrder], fills: &[u64]) {
+    let order_map: Arc<DashMap<OrderId, CompactOrder>> = Arc::new(DashMap::new());
+
     // Write operations - Create orders
-    for i in 0..data_size {
-        let order_id = format!("ORD_{}", i);
-        let symbol = format!("STOCK_{}", i % 1000);
-        let client_id = format!("CLIENT_{}", i % 100);
-        let order = Order::new(i as u64, symbol, client_id);
-        order_map.insert(order_id, order);
+    for order in orders {
+        order_map.insert(order.order_id, *order);
     }
-    
+
     // Read operations - Query orders
-    for i in 0..data_size {
-        let order_id = format!("ORD_{}", i);
-        black_box(order_map.get(&order_id));
+    for order in orders {
+        black_box(order_map.get(&order.order_id));
     }
-    
+
     // Update operations - Fill orders
-    for i in 0..data_size {
-        let order_id = format!("ORD_{}", i);
-        if let Some(mut order) = order_map.get_mut(&order_id) {
+    for (order, &fill_qty) in orders.iter().zip(fills) {
+        if let Some(mut order) = order_map.get_mut(&order.order_id) {
             if order.remaining_quantity > 0 {
-                let fill_qty = rng.gen_range(1..=order.remaining_quantity);
                 order.update_fill(fill_qty);
             }
         }
     }
 }
 
-// Async operations
-async fn async_order_operations(data_size: usize) {
-    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
-    
+async fn async_interned_order_operations(orders: &[CompactOrder], fills: &[u64]) {
+    let order_map: Arc<DashMap<OrderId, CompactOrder>> = Arc::new(DashMap::new());
+
     // Write operations - Create orders
-    let write_tasks: Vec<_> = (0..data_size)
-        .map(|i| {
+    let write_tasks: Vec<_> = orders
+        .iter()
+        .map(|&order| {
             let order_map = order_map.clone();
             async move {
-                let order_id = format!("ORD_{}", i);
-                let symbol = format!("STOCK_{}", i % 1000);
-                let client_id = format!("CLIENT_{}", i % 100);
-                let order = Order::new(i as u64, symbol, client_id);
-                order_map.insert(order_id, order);
+                order_map.insert(order.order_id, order);
             }
         })
         .collect();
-    
+
     join_all(write_tasks).await;
-    
+
     // Read operations - Query orders
-    let read_tasks: Vec<_> = (0..data_size)
-        .map(|i| {
+    let read_tasks: Vec<_> = orders
+        .iter()
+        .map(|order| {
             let order_map = order_map.clone();
+            let order_id = order.order_id;
             async move {
-                let order_id = format!("ORD_{}", i);
                 black_box(order_map.get(&order_id));
             }
         })
         .collect();
-    
+
     join_all(read_tasks).await;
-    
+
     // Update operations - Fill orders
-    let update_tasks: Vec<_> = (0..data_size)
-        .map(|i| {
+    let update_tasks: Vec<_> = orders
+        .iter()
+        .zip(fills)
+        .map(|(order, &fill_qty)| {
             let order_map = order_map.clone();
+            let order_id = order.order_id;
             async move {
-                let order_id = format!("ORD_{}", i);
                 if let Some(mut order) = order_map.get_mut(&order_id) {
                     if order.remaining_quantity > 0 {
-                        let mut rng = thread_rng();
-                        let fill_qty = rng.gen_range(1..=order.remaining_quantity);
                         order.update_fill(fill_qty);
                     }
                 }
             }
         })
         .collect();
-    
+
     join_all(update_tasks).await;
 }
 
+// Latency capture: the string-keyed workloads again, with every insert, get
+// and update timed individually into HDR histograms
+const LATENCY_SAMPLES_PER_SCENARIO: usize = 100_000;
+
+fn sync_order_latencies(workload: &OrderWorkload, recorder: &mut LatencyRecorder) {
+    let mut order_map: HashMap<String, Order> = HashMap::new();
+
+    for (i, order) in workload.orders.iter().enumerate() {
+        let order_id = format!("ORD_{}", i);
+        recorder.time(Operation::Insert, || order_map.insert(order_id, order.clone()));
+    }
+
+    for i in 0..workload.len() {
+        let order_id = format!("ORD_{}", i);
+        recorder.time(Operation::Get, || black_box(order_map.get(&order_id).map(|order| order.remaining_quantity)));
+    }
+
+    for (i, &fill_qty) in workload.fills.iter().enumerate() {
+        let order_id = format!("ORD_{}", i);
+        recorder.time(Operation::Update, || {
+            if let Some(order) = order_map.get_mut(&order_id) {
+                if order.remaining_quantity > 0 {
+                    order.update_fill(fill_qty);
+                }
+            }
+        });
+    }
+}
+
+fn sync_concurrent_order_latencies(workload: &OrderWorkload, recorder: &mut LatencyRecorder) {
+    let order_map: DashMap<String, Order> = DashMap::new();
+
+    for (i, order) in workload.orders.iter().enumerate() {
+        recorder.time(Operation::Insert, || scenarios::insert_order(&order_map, i, order.clone()));
+    }
+
+    for i in 0..workload.len() {
+        recorder.time(Operation::Get, || black_box(scenarios::query_order(&order_map, i)));
+    }
+
+    for (i, &fill_qty) in workload.fills.iter().enumerate() {
+        recorder.time(Operation::Update, || scenarios::fill_order(&order_map, i, fill_qty));
+    }
+}
+
+// Each operation is a future built like the ones async_order_operations joins
+// and is joined in the same batch. A sample runs from creating the future to
+// its completion, so the wait for join_all to poll it is part of it.
+async fn async_order_latencies(workload: &OrderWorkload, recorder: &mut LatencyRecorder) {
+    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
+
+    let write_tasks: Vec<_> = workload
+        .orders
+        .iter()
+        .enumerate()
+        .map(|(i, order)| {
+            let start = Instant::now();
+            let order_map = order_map.clone();
+            async move {
+                scenarios::insert_order(&order_map, i, order.clone());
+                start.elapsed()
+            }
+        })
+        .collect();
+
+    for elapsed in join_all(write_tasks).await {
+        recorder.record(Operation::Insert, elapsed);
+    }
+
+    let read_tasks: Vec<_> = (0..workload.len())
+        .map(|i| {
+            let start = Instant::now();
+            let order_map = order_map.clone();
+            async move {
+                black_box(scenarios::query_order(&order_map, i));
+                start.elapsed()
+            }
+        })
+        .collect();
+
+    for elapsed in join_all(read_tasks).await {
+        recorder.record(Operation::Get, elapsed);
+    }
+
+    let update_tasks: Vec<_> = workload
+        .fills
+        .iter()
+        .enumerate()
+        .map(|(i, &fill_qty)| {
+            let start = Instant::now();
+            let order_map = order_map.clone();
+            async move {
+                scenarios::fill_order(&order_map, i, fill_qty);
+                start.elapsed()
+            }
+        })
+        .collect();
+
+    for elapsed in join_all(update_tasks).await {
+        recorder.record(Operation::Update, elapsed);
+    }
+}
+
+// Repeats a timed pass until the scenario has enough samples for stable tails
+fn capture_latencies(scenario: String, size: usize, mut pass: impl FnMut(&mut LatencyRecorder)) -> Vec<LatencySummary> {
+    let mut recorder = LatencyRecorder::new();
+    for _ in 0..(LATENCY_SAMPLES_PER_SCENARIO / size).max(1) {
+        pass(&mut recorder);
+    }
+    recorder.summaries(&scenario)
+}
+
+fn save_latencies(summaries: &[LatencySummary]) {
+    if summaries.is_empty() {
+        return;
+    }
+    if let Err(err) = latency::record_summaries(&criterion_dir(), summaries) {
+        eprintln!("warning: could not record latency percentiles: {}", err);
+    }
+}
+
 // Order book flattening operations
 fn sync_order_flatten(nested_orders: &HashMap<String, HashMap<String, Order>>) -> HashMap<String, Order> {
     let mut flattened = HashMap::new();
-    
+
     for (exchange, orders) in nested_orders {
         for (order_id, order) in orders {
             let flat_key = format!("{}:{}", exchange, order_id);
             flattened.insert(flat_key, order.clone());
         }
     }
-    
+
     flattened
 }
 
@@ -228,60 +290,69 @@ async fn async_order_flatten(nested_orders: &HashMap<String, HashMap<String, Ord
             }
         })
         .collect();
-    
+
     let results = join_all(tasks).await;
     let mut flattened = HashMap::new();
-    
+
     for result in results {
         flattened.extend(result);
     }
-    
+
     flattened
 }
 
-fn create_nested_order_data(exchanges: usize, orders_per_exchange: usize) -> HashMap<String, HashMap<String, Order>> {
+fn create_nested_order_data(seed: u64, exchanges: usize, orders_per_exchange: usize, symbols: usize, clients: usize) -> HashMap<String, HashMap<String, Order>> {
+    let mut generator = WorkloadGenerator::new(seed);
     let mut nested_orders = HashMap::new();
-    
+
     for i in 0..exchanges {
         let exchange = format!("EXCHANGE_{}", i);
         let mut orders = HashMap::new();
-        
+
         for j in 0..orders_per_exchange {
             let order_id = format!("ORD_{}_{}", i, j);
-            let symbol = format!("STOCK_{}", j % 100);
-            let client_id = format!("CLIENT_{}", j % 50);
-            let order = Order::new((i * orders_per_exchange + j) as u64, symbol, client_id);
+            let symbol = format!("STOCK_{}", j % symbols);
+            let client_id = format!("CLIENT_{}", j % clients);
+            let order = generator.next_order((i * orders_per_exchange + j) as u64, symbol, client_id);
             orders.insert(order_id, order);
         }
-        
+
         nested_orders.insert(exchange, orders);
     }
-    
+
     nested_orders
 }
 
 // Trillion-scale transaction simulation
-fn sync_trillion_transactions(batch_size: usize, batches: usize) {
+// Orders are generated on the fly from the seed since the stream is too large to pre-generate
+// Each order is generated from its own stream where it is processed, so the
+// sync and async variants do the same work and nothing is held up front
+fn trillion_order(seed: u64, order_id: usize, symbols: usize, clients: usize) -> (Order, u64) {
+    let mut generator = WorkloadGenerator::for_stream(seed, order_id as u64);
+    let symbol = format!("STOCK_{}", order_id % symbols);
+    let client_id = format!("CLIENT_{}", order_id % clients);
+    let order = generator.next_order(order_id as u64, symbol, client_id);
+    let fill_qty = generator.next_fill(order.remaining_quantity);
+    (order, fill_qty)
+}
+
+fn sync_trillion_transactions(seed: u64, batch_size: usize, batches: usize, symbols: usize, clients: usize) {
     let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
-    let mut rng = thread_rng();
-    
+
     for batch in 0..batches {
         for i in 0..batch_size {
             let order_id = format!("ORD_{}_{}", batch, i);
-            let symbol = format!("STOCK_{}", i % 1000);
-            let client_id = format!("CLIENT_{}", i % 100);
-            let order = Order::new((batch * batch_size + i) as u64, symbol, client_id);
+            let (order, fill_qty) = trillion_order(seed, batch * batch_size + i, symbols, clients);
             order_map.insert(order_id.clone(), order);
-            
+
             // Simulate order processing
             if let Some(mut order) = order_map.get_mut(&order_id) {
                 if order.remaining_quantity > 0 {
-                    let fill_qty = rng.gen_range(1..=order.remaining_quantity);
                     order.update_fill(fill_qty);
                 }
             }
         }
-        
+
         // Cleanup filled orders periodically
         if batch % 1000 == 0 {
             order_map.retain(|_, order| order.status != OrderStatus::Filled);
@@ -289,34 +360,31 @@ fn sync_trillion_transactions(batch_size: usize, batches: usize) {
     }
 }
 
-async fn async_trillion_transactions(batch_size: usize, batches: usize) {
+// The batch futures own their inputs, so any task strategy can drive them
+async fn async_trillion_transactions(seed: u64, batch_size: usize, batches: usize, symbols: usize, clients: usize, tasks: TaskStrategy) {
     let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
-    
+
     for batch in 0..batches {
         let batch_tasks: Vec<_> = (0..batch_size)
             .map(|i| {
                 let order_map = order_map.clone();
                 async move {
                     let order_id = format!("ORD_{}_{}", batch, i);
-                    let symbol = format!("STOCK_{}", i % 1000);
-                    let client_id = format!("CLIENT_{}", i % 100);
-                    let order = Order::new((batch * batch_size + i) as u64, symbol, client_id);
+                    let (order, fill_qty) = trillion_order(seed, batch * batch_size + i, symbols, clients);
                     order_map.insert(order_id.clone(), order);
-                    
+
                     // Simulate order processing
                     if let Some(mut order) = order_map.get_mut(&order_id) {
                         if order.remaining_quantity > 0 {
-                            let mut rng = thread_rng();
-                            let fill_qty = rng.gen_range(1..=order.remaining_quantity);
                             order.update_fill(fill_qty);
                         }
                     }
                 }
             })
             .collect();
-        
-        join_all(batch_tasks).await;
-        
+
+        scenarios::run_tasks(tasks, batch_tasks).await;
+
         // Cleanup filled orders periodically
         if batch % 1000 == 0 {
             order_map.retain(|_, order| order.status != OrderStatus::Filled);
@@ -324,205 +392,873 @@ async fn async_trillion_transactions(batch_size: usize, batches: usize) {
     }
 }
 
-fn bench_sync_operations(c: &mut Criterion) {
-    let mut group = c.benchmark_group("sync_order_operations");
-    
-    for size in [100, 1000, 10000].iter() {
-        group.bench_with_input(
-            BenchmarkId::new("single_threaded", size),
-            size,
-            |b, &size| {
-                b.iter(|| sync_order_operations(size));
-            },
-        );
-        
-        group.bench_with_input(
-            BenchmarkId::new("concurrent", size),
-            size,
-            |b, &size| {
-                b.iter(|| sync_concurrent_order_operations(size));
-            },
-        );
-    }
-    
+// Order book matching operations
+struct BookWorkload {
+    orders: Vec<Order>,
+    // (order_id, new_quantity, new_price) for every 5th order
+    modifies: Vec<(u64, u64, Price)>,
+    // Every 3rd order id
+    cancels: Vec<u64>,
+    symbols: usize,
+}
+
+impl BookWorkload {
+    fn generate(seed: u64, data_size: usize, symbols: usize, clients: usize) -> Self {
+        let mut generator = WorkloadGenerator::new(seed);
+        let orders = (0..data_size)
+            .map(|i| {
+                let symbol = format!("STOCK_{}", i % symbols);
+                let client_id = format!("CLIENT_{}", i % clients);
+                generator.next_book_order(i as u64, symbol, client_id)
+            })
+            .collect();
+        let modifies = (0..data_size)
+            .step_by(5)
+            .map(|i| {
+                let rng = generator.rng();
+                (i as u64, rng.gen_range(1000..2000), Price::from_cents(rng.gen_range(9_900..10_100)))
+            })
+            .collect();
+        let cancels = (0..data_size).step_by(3).map(|i| i as u64).collect();
+
+        Self { orders, modifies, cancels, symbols }
+    }
+
+    // Symbol the order was generated with, to find its book
+    fn symbol(&self, order_id: u64) -> String {
+        format!("STOCK_{}", order_id % self.symbols as u64)
+    }
+}
+
+fn sync_order_book_operations<K: PriceLevelKey>(workload: &BookWorkload, specs: &SymbolSpecs) {
+    let mut books: OrderBooks<K> = OrderBooks::with_specs(specs.clone());
+
+    // Submit orders - match and rest
+    for order in &workload.orders {
+        black_box(books.add_order(order.clone()).ok());
+    }
+
+    // Modify every 5th order that is still resting
+    for &(order_id, new_quantity, new_price) in &workload.modifies {
+        let symbol = workload.symbol(order_id);
+        black_box(books.modify_order(&symbol, order_id, new_quantity, new_price).ok());
+    }
+
+    // Cancel every 3rd order that is still resting
+    for &order_id in &workload.cancels {
+        let symbol = workload.symbol(order_id);
+        black_box(books.cancel_order(&symbol, order_id).ok());
+    }
+}
+
+fn sync_concurrent_order_book_operations<K: PriceLevelKey>(workload: &BookWorkload, specs: &SymbolSpecs) {
+    let books: Arc<DashMap<String, OrderBook<K>>> = Arc::new(DashMap::new());
+
+    for order in &workload.orders {
+        let symbol = order.symbol.clone();
+        let spec = *specs.get(&symbol);
+        let mut book = books.entry(symbol.clone()).or_insert_with(|| OrderBook::with_spec(symbol, spec));
+        black_box(book.add_order(order.clone()).ok());
+    }
+
+    for &(order_id, new_quantity, new_price) in &workload.modifies {
+        if let Some(mut book) = books.get_mut(&workload.symbol(order_id)) {
+            black_box(book.modify_order(order_id, new_quantity, new_price).ok());
+        }
+    }
+
+    for &order_id in &workload.cancels {
+        if let Some(mut book) = books.get_mut(&workload.symbol(order_id)) {
+            black_box(book.cancel_order(order_id).ok());
+        }
+    }
+}
+
+async fn async_order_book_operations<K: PriceLevelKey>(workload: &BookWorkload, specs: &SymbolSpecs) {
+    let books: Arc<DashMap<String, OrderBook<K>>> = Arc::new(DashMap::new());
+
+    let submit_tasks: Vec<_> = workload
+        .orders
+        .iter()
+        .map(|order| {
+            let books = books.clone();
+            async move {
+                let symbol = order.symbol.clone();
+                let spec = *specs.get(&symbol);
+                let mut book = books.entry(symbol.clone()).or_insert_with(|| OrderBook::with_spec(symbol, spec));
+                black_box(book.add_order(order.clone()).ok());
+            }
+        })
+        .collect();
+
+    join_all(submit_tasks).await;
+
+    let modify_tasks: Vec<_> = workload
+        .modifies
+        .iter()
+        .map(|&(order_id, new_quantity, new_price)| {
+            let books = books.clone();
+            async move {
+                if let Some(mut book) = books.get_mut(&workload.symbol(order_id)) {
+                    black_box(book.modify_order(order_id, new_quantity, new_price).ok());
+                }
+            }
+        })
+        .collect();
+
+    join_all(modify_tasks).await;
+
+    let cancel_tasks: Vec<_> = workload
+        .cancels
+        .iter()
+        .map(|&order_id| {
+            let books = books.clone();
+            async move {
+                if let Some(mut book) = books.get_mut(&workload.symbol(order_id)) {
+                    black_box(book.cancel_order(order_id).ok());
+                }
+            }
+        })
+        .collect();
+
+    join_all(cancel_tasks).await;
+}
+
+// Stop trigger operations
+fn stop_orders(seed: u64, stops: usize, clients: usize) -> Vec<Order> {
+    let mut generator = WorkloadGenerator::new(seed);
+    (0..stops)
+        .map(|i| generator.next_stop_order(i as u64, "STOCK_0".to_string(), format!("CLIENT_{}", i % clients)))
+        .collect()
+}
+
+fn sync_stop_triggers(stops: &[Order], prices: &[Price]) {
+    let mut triggers = StopTriggers::new();
+
+    for order in stops {
+        triggers.park(order.clone()).unwrap();
+    }
+
+    for &price in prices {
+        black_box(triggers.on_trade(price));
+    }
+}
+
+async fn async_stop_triggers(stops: &[Order], prices: &[Price]) {
+    let (notifier, mut triggered, handle) = TriggerNotifier::spawn();
+
+    for order in stops {
+        notifier.park(order.clone()).unwrap();
+    }
+
+    for &price in prices {
+        notifier.publish_trade(price).unwrap();
+    }
+
+    drop(notifier);
+    while let Some(outcome) = triggered.recv().await {
+        black_box(outcome);
+    }
+    black_box(handle.await.unwrap());
+}
+
+// HFT simulation with the futures driven by `tasks`; they own their orders
+// so they can be spawned
+async fn spawned_hft_orders(population: &OrderWorkload, updates: &[(usize, u64)], tasks: TaskStrategy) {
+    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
+
+    // Initial order population
+    let init_tasks: Vec<_> = population
+        .orders
+        .iter()
+        .enumerate()
+        .map(|(i, order)| {
+            let order_map = order_map.clone();
+            let order = order.clone();
+            async move {
+                let order_id = format!("ORD_{}", i);
+                order_map.insert(order_id, order);
+            }
+        })
+        .collect();
+
+    scenarios::run_tasks(tasks, init_tasks).await;
+
+    // High frequency order updates
+    let update_tasks: Vec<_> = updates
+        .iter()
+        .map(|&(index, fill_qty)| {
+            let order_map = order_map.clone();
+            async move {
+                let order_id = format!("ORD_{}", index);
+                if let Some(mut order) = order_map.get_mut(&order_id) {
+                    if order.remaining_quantity > 0 {
+                        order.update_fill(fill_qty);
+                    }
+                }
+            }
+        })
+        .collect();
+
+    scenarios::run_tasks(tasks, update_tasks).await;
+}
+
+// HFT simulation: populate a DashMap, then apply random fills
+fn sync_hft_orders(population: &OrderWorkload, updates: &[(usize, u64)]) {
+    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
+
+    // Initial order population
+    for (i, order) in population.orders.iter().enumerate() {
+        let order_id = format!("ORD_{}", i);
+        order_map.insert(order_id, order.clone());
+    }
+
+    // High frequency order updates
+    for &(index, fill_qty) in updates {
+        let order_id = format!("ORD_{}", index);
+        if let Some(mut order) = order_map.get_mut(&order_id) {
+            if order.remaining_quantity > 0 {
+                order.update_fill(fill_qty);
+            }
+        }
+    }
+}
+
+async fn async_hft_orders(population: &OrderWorkload, updates: &[(usize, u64)]) {
+    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
+
+    // Initial order population
+    let init_tasks: Vec<_> = population
+        .orders
+        .iter()
+        .enumerate()
+        .map(|(i, order)| {
+            let order_map = order_map.clone();
+            async move {
+                let order_id = format!("ORD_{}", i);
+                order_map.insert(order_id, order.clone());
+            }
+        })
+        .collect();
+
+    join_all(init_tasks).await;
+
+    // High frequency order updates
+    let update_tasks: Vec<_> = updates
+        .iter()
+        .map(|&(index, fill_qty)| {
+            let order_map = order_map.clone();
+            async move {
+                let order_id = format!("ORD_{}", index);
+                if let Some(mut order) = order_map.get_mut(&order_id) {
+                    if order.remaining_quantity > 0 {
+                        order.update_fill(fill_qty);
+                    }
+                }
+            }
+        })
+        .collect();
+
+    join_all(update_tasks).await;
+}
+
+// Interned HFT simulation: the same population and updates keyed by the
+// numeric order id the string variants format as "ORD_{index}"
+fn sync_interned_hft_orders(population: &[CompactOrder], updates: &[(usize, u64)]) {
+    let order_map: Arc<DashMap<OrderId, CompactOrder>> = Arc::new(DashMap::new());
+
+    // Initial order population
+    for order in population {
+        order_map.insert(order.order_id, *order);
+    }
+
+    // High frequency order updates
+    for &(index, fill_qty) in updates {
+        if let Some(mut order) = order_map.get_mut(&OrderId(index as u64)) {
+            if order.remaining_quantity > 0 {
+                order.update_fill(fill_qty);
+            }
+        }
+    }
+}
+
+async fn async_interned_hft_orders(population: &[CompactOrder], updates: &[(usize, u64)]) {
+    let order_map: Arc<DashMap<OrderId, CompactOrder>> = Arc::new(DashMap::new());
+
+    // Initial order population
+    let init_tasks: Vec<_> = population
+        .iter()
+        .map(|&order| {
+            let order_map = order_map.clone();
+            async move {
+                order_map.insert(order.order_id, order);
+            }
+        })
+        .collect();
+
+    join_all(init_tasks).await;
+
+    // High frequency order updates
+    let update_tasks: Vec<_> = updates
+        .iter()
+        .map(|&(index, fill_qty)| {
+            let order_map = order_map.clone();
+            async move {
+                if let Some(mut order) = order_map.get_mut(&OrderId(index as u64)) {
+                    if order.remaining_quantity > 0 {
+                        order.update_fill(fill_qty);
+                    }
+                }
+            }
+        })
+        .collect();
+
+    join_all(update_tasks).await;
+}
+
+// Open-loop requests: one order inserted and filled per scheduled send,
+// cycling through a fixed workload so the maps stay bounded
+fn open_loop_hashmap_request(order_map: &mut HashMap<String, Order>, workload: &OrderWorkload, i: u64) {
+    let index = i as usize % workload.len();
+    let order_id = format!("ORD_{}", index);
+    order_map.insert(order_id.clone(), workload.orders[index].clone());
+    if let Some(order) = order_map.get_mut(&order_id) {
+        if order.remaining_quantity > 0 {
+            order.update_fill(workload.fills[index]);
+        }
+    }
+}
+
+fn open_loop_dashmap_request(order_map: &DashMap<String, Order>, workload: &OrderWorkload, i: u64) {
+    let index = i as usize % workload.len();
+    let order_id = format!("ORD_{}", index);
+    order_map.insert(order_id.clone(), workload.orders[index].clone());
+    if let Some(mut order) = order_map.get_mut(&order_id) {
+        if order.remaining_quantity > 0 {
+            order.update_fill(workload.fills[index]);
+        }
+    }
+}
+
+// Default Criterion sample size
+const CRITERION_SAMPLE_SIZE: usize = 100;
+
+// Samples per benchmark of the scenario, Criterion's default when the file
+// does not set one
+fn measured_samples(settings: &CriterionSettings) -> usize {
+    settings.sample_size.unwrap_or(CRITERION_SAMPLE_SIZE)
+}
+
+// Latencies and send totals per iter_custom batch. Criterion's warm-up calls
+// iter_custom as well, but the measurement is always the last `samples`
+// batches, so only those are reported.
+struct OpenLoopBatches {
+    samples: usize,
+    batches: VecDeque<(LatencyRecorder, RunStats)>,
+}
+
+impl OpenLoopBatches {
+    fn new(samples: usize) -> Self {
+        Self {
+            samples,
+            batches: VecDeque::new(),
+        }
+    }
+
+    // Runs one batch with a fresh recorder
+    fn record(&mut self, batch: impl FnOnce(&mut LatencyRecorder) -> RunStats) -> RunStats {
+        let mut recorder = LatencyRecorder::new();
+        let stats = batch(&mut recorder);
+        if self.batches.len() == self.samples {
+            self.batches.pop_front();
+        }
+        self.batches.push_back((recorder, stats));
+        stats
+    }
+
+    fn report(&self, scenario: &str, schedule: Schedule) -> Vec<LatencySummary> {
+        let mut recorder = LatencyRecorder::new();
+        let mut totals = RunStats::default();
+        for (batch_recorder, stats) in &self.batches {
+            recorder.merge(batch_recorder);
+            totals.merge(stats);
+        }
+        report_open_loop(scenario, schedule, &totals);
+        recorder.summaries(scenario)
+    }
+}
+
+fn report_open_loop(scenario: &str, schedule: Schedule, stats: &RunStats) {
+    // Nothing ran when the scenario was filtered out
+    if stats.orders == 0 {
+        return;
+    }
+    println!(
+        "{}: target {} orders/sec, achieved {:.0} orders/sec, {} of {} orders sent behind schedule",
+        scenario,
+        schedule.rate_per_sec(),
+        stats.achieved_rate(),
+        stats.late,
+        stats.orders
+    );
+}
+
+// Criterion overrides from the scenario file
+fn configure_group<M: Measurement>(group: &mut BenchmarkGroup<'_, M>, settings: &CriterionSettings) {
+    if let Some(sample_size) = settings.sample_size {
+        group.sample_size(sample_size);
+    }
+    if let Some(warm_up_time) = settings.warm_up_time() {
+        group.warm_up_time(warm_up_time);
+    }
+    if let Some(measurement_time) = settings.measurement_time() {
+        group.measurement_time(measurement_time);
+    }
+}
+
+// Runtimes the tokio execution runs on, with the label each one adds to the
+// benchmark function name. Runtimes this build cannot create are skipped.
+fn runtimes(spec: &ScenarioSpec) -> Vec<(String, Runtime)> {
+    if spec.function(Scenario::Tokio).is_none() {
+        return Vec::new();
+    }
+    spec.runtimes
+        .iter()
+        .filter_map(|settings| match settings.build() {
+            Ok(runtime) => Some((settings.label(), runtime)),
+            Err(err) => {
+                eprintln!("warning: skipping {} on the {} runtime: {}", spec.name, settings.describe(), err);
+                None
+            }
+        })
+        .collect()
+}
+
+fn bench_order_operations(c: &mut Criterion, spec: &ScenarioSpec, sizes: &[usize], symbols: usize, clients: usize) {
+    let mut group = c.benchmark_group(&spec.name);
+    configure_group(&mut group, &spec.criterion);
+    let runtimes = runtimes(spec);
+    // The sync executions ignore the runtime and run once
+    let sync_runtime = [(String::new(), RuntimeSettings::current_thread().build().unwrap())];
+    let seed = workload_seed();
+    let strategies = spec.key_strategies().unwrap_or_else(|err| panic!("{}", err));
+    let mut latencies = Vec::new();
+
+    for &size in sizes {
+        let workload = OrderWorkload::generate(seed, size, symbols, clients);
+        let compact = workload.compact_orders(&mut SymbolTable::new());
+
+        for (execution, function) in spec.functions() {
+            // join_all (and the sync executions) with latency capture and both key strategies
+            let execution_runtimes = if execution != Scenario::Tokio {
+                &sync_runtime[..]
+            } else if spec.tasks.contains(&TaskStrategy::JoinAll) {
+                &runtimes[..]
+            } else {
+                &[]
+            };
+            for (label, rt) in execution_runtimes {
+                // Criterion only runs the closures of the ids its filter
+                // selects, so the percentiles are captured from inside them,
+                // once for whichever key strategy runs first
+                let mut captured = false;
+                let mut capture = || {
+                    if std::mem::replace(&mut captured, true) {
+                        return;
+                    }
+                    latencies.extend(capture_latencies(
+                        format!("{}/{}{}/{}", spec.name, function, label, size),
+                        size,
+                        |recorder| match execution {
+                            Scenario::Sync => sync_order_latencies(&workload, recorder),
+                            Scenario::DashMap => sync_concurrent_order_latencies(&workload, recorder),
+                            Scenario::Tokio => rt.block_on(async_order_latencies(&workload, recorder)),
+                        },
+                    ));
+                };
+
+                for strategy in &strategies {
+                    let id = BenchmarkId::new(format!("{}{}{}", function, strategy.suffix(), label), size);
+                    match strategy {
+                        KeyStrategy::Strings => {
+                            group.bench_with_input(id, &workload, |b, workload| {
+                                capture();
+                                b.iter(|| execution.execute(workload, rt));
+                            });
+                        }
+                        KeyStrategy::Interned => {
+                            group.bench_with_input(id, &compact, |b, compact| {
+                                capture();
+                                b.iter(|| match execution {
+                                    Scenario::Sync => sync_interned_order_operations(compact, &workload.fills),
+                                    Scenario::DashMap => sync_concurrent_interned_order_operations(compact, &workload.fills),
+                                    Scenario::Tokio => rt.block_on(async_interned_order_operations(compact, &workload.fills)),
+                                });
+                            });
+                        }
+                    }
+                }
+            }
+
+            // Spawning task strategies, string keys only
+            if execution == Scenario::Tokio {
+                for &tasks in spec.tasks.iter().filter(|&&tasks| tasks != TaskStrategy::JoinAll) {
+                    for (label, rt) in &runtimes {
+                        group.bench_with_input(
+                            BenchmarkId::new(format!("{}{}{}", function, tasks.suffix(), label), size),
+                            &workload,
+                            |b, workload| {
+                                b.iter(|| execution.execute_with(tasks, workload, rt));
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     group.finish();
+
+    save_latencies(&latencies);
 }
 
-fn bench_async_operations(c: &mut Criterion) {
-    let mut group = c.benchmark_group("async_order_operations");
-    let rt = Runtime::new().unwrap();
-    
-    for size in [100, 1000, 10000].iter() {
-        group.bench_with_input(
-            BenchmarkId::new("async", size),
-            size,
-            |b, &size| {
-                b.iter(|| {
-                    rt.block_on(async_order_operations(size));
-                });
-            },
-        );
+fn bench_order_flattening(c: &mut Criterion, spec: &ScenarioSpec, exchange_counts: &[usize], order_counts: &[usize], symbols: usize, clients: usize) {
+    let mut group = c.benchmark_group(&spec.name);
+    configure_group(&mut group, &spec.criterion);
+    let runtimes = runtimes(spec);
+    let seed = workload_seed();
+
+    for &exchanges in exchange_counts {
+        for &orders in order_counts {
+            let nested_orders = create_nested_order_data(seed, exchanges, orders, symbols, clients);
+
+            if let Some(function) = spec.function(Scenario::Sync) {
+                group.bench_with_input(
+                    BenchmarkId::new(function, format!("{}x{}", exchanges, orders)),
+                    &nested_orders,
+                    |b, data| {
+                        b.iter(|| sync_order_flatten(data));
+                    },
+                );
+            }
+
+            if let Some(function) = spec.function(Scenario::Tokio) {
+                for (label, rt) in &runtimes {
+                    group.bench_with_input(
+                        BenchmarkId::new(format!("{}{}", function, label), format!("{}x{}", exchanges, orders)),
+                        &nested_orders,
+                        |b, data| {
+                            b.iter(|| {
+                                rt.block_on(async_order_flatten(data));
+                            });
+                        },
+                    );
+                }
+            }
+        }
     }
-    
+
     group.finish();
 }
 
-fn bench_order_flattening(c: &mut Criterion) {
-    let mut group = c.benchmark_group("order_flattening");
-    let rt = Runtime::new().unwrap();
-    
-    for exchanges in [5, 10, 20].iter() {
-        for orders in [100, 500, 1000].iter() {
-            let nested_orders = create_nested_order_data(*exchanges, *orders);
-            
+fn bench_order_books<K: PriceLevelKey + Send + Sync>(c: &mut Criterion, spec: &ScenarioSpec, sizes: &[usize], symbols: usize, clients: usize) {
+    let mut group = c.benchmark_group(&spec.name);
+    configure_group(&mut group, &spec.criterion);
+    let runtimes = runtimes(spec);
+    let seed = workload_seed();
+    let specs = SymbolSpecs::default();
+
+    for &size in sizes {
+        let workload = BookWorkload::generate(seed, size, symbols, clients);
+
+        if let Some(function) = spec.function(Scenario::Sync) {
             group.bench_with_input(
-                BenchmarkId::new("sync", format!("{}x{}", exchanges, orders)),
-                &nested_orders,
-                |b, data| {
-                    b.iter(|| sync_order_flatten(data));
+                BenchmarkId::new(function, size),
+                &workload,
+                |b, workload| {
+                    b.iter(|| sync_order_book_operations::<K>(workload, &specs));
                 },
             );
-            
+        }
+
+        if let Some(function) = spec.function(Scenario::DashMap) {
             group.bench_with_input(
-                BenchmarkId::new("async", format!("{}x{}", exchanges, orders)),
-                &nested_orders,
-                |b, data| {
-                    b.iter(|| {
-                        rt.block_on(async_order_flatten(data));
-                    });
+                BenchmarkId::new(function, size),
+                &workload,
+                |b, workload| {
+                    b.iter(|| sync_concurrent_order_book_operations::<K>(workload, &specs));
                 },
             );
         }
+
+        if let Some(function) = spec.function(Scenario::Tokio) {
+            for (label, rt) in &runtimes {
+                group.bench_with_input(
+                    BenchmarkId::new(format!("{}{}", function, label), size),
+                    &workload,
+                    |b, workload| {
+                        b.iter(|| {
+                            rt.block_on(async_order_book_operations::<K>(workload, &specs));
+                        });
+                    },
+                );
+            }
+        }
     }
-    
+
     group.finish();
 }
 
-fn bench_hft_simulation(c: &mut Criterion) {
-    let mut group = c.benchmark_group("hft_order_simulation");
-    let rt = Runtime::new().unwrap();
-    
-    // Simulate high-frequency trading order scenarios
-    let order_updates = 10000;
-    
-    group.bench_function("sync_hft_orders", |b| {
-        b.iter(|| {
-            let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
-            let mut rng = thread_rng();
-            
-            // Initial order population
-            for i in 0..1000 {
-                let order_id = format!("ORD_{}", i);
-                let symbol = format!("STOCK_{}", i % 100);
-                let client_id = format!("CLIENT_{}", i % 50);
-                let order = Order::new(i as u64, symbol, client_id);
-                order_map.insert(order_id, order);
+fn bench_stop_triggers(c: &mut Criterion, spec: &ScenarioSpec, sizes: &[usize], trades: usize, clients: usize) {
+    let mut group = c.benchmark_group(&spec.name);
+    configure_group(&mut group, &spec.criterion);
+    let runtimes = runtimes(spec);
+    let seed = workload_seed();
+    let prices = WorkloadGenerator::new(seed).trade_prices(trades);
+
+    for &size in sizes {
+        let stops = stop_orders(seed, size, clients);
+
+        if let Some(function) = spec.function(Scenario::Sync) {
+            group.bench_with_input(
+                BenchmarkId::new(function, size),
+                &stops,
+                |b, stops| {
+                    b.iter(|| sync_stop_triggers(stops, &prices));
+                },
+            );
+        }
+
+        if let Some(function) = spec.function(Scenario::Tokio) {
+            for (label, rt) in &runtimes {
+                group.bench_with_input(
+                    BenchmarkId::new(format!("{}{}", function, label), size),
+                    &stops,
+                    |b, stops| {
+                        b.iter(|| {
+                            rt.block_on(async_stop_triggers(stops, &prices));
+                        });
+                    },
+                );
             }
-            
-            // High frequency order updates
-            for _ in 0..order_updates {
-                let order_id = format!("ORD_{}", rng.gen_range(0..1000));
-                if let Some(mut order) = order_map.get_mut(&order_id) {
-                    if order.remaining_quantity > 0 {
-                        let fill_qty = rng.gen_range(1..=order.remaining_quantity);
-                        order.update_fill(fill_qty);
-                    }
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_hft_simulation(c: &mut Criterion, spec: &ScenarioSpec, orders: usize, order_updates: usize, symbols: usize, clients: usize) {
+    let mut group = c.benchmark_group(&spec.name);
+    configure_group(&mut group, &spec.criterion);
+    let runtimes = runtimes(spec);
+    let seed = workload_seed();
+
+    // Simulate high-frequency trading order scenarios
+    let population = OrderWorkload::generate(seed, orders, symbols, clients);
+    let compact = population.compact_orders(&mut SymbolTable::new());
+    let strategies = spec.key_strategies().unwrap_or_else(|err| panic!("{}", err));
+
+    // (order index, fill quantity) pairs; fills are clamped to the remaining quantity
+    let mut generator = WorkloadGenerator::new(seed);
+    let updates: Vec<(usize, u64)> = (0..order_updates)
+        .map(|_| {
+            let rng = generator.rng();
+            (rng.gen_range(0..population.len()), rng.gen_range(1..10000))
+        })
+        .collect();
+
+    if let Some(function) = spec.function(Scenario::DashMap) {
+        for strategy in &strategies {
+            group.bench_function(format!("{}{}", function, strategy.suffix()), |b| {
+                b.iter(|| match strategy {
+                    KeyStrategy::Strings => sync_hft_orders(&population, &updates),
+                    KeyStrategy::Interned => sync_interned_hft_orders(&compact, &updates),
+                });
+            });
+        }
+    }
+
+    if let Some(function) = spec.function(Scenario::Tokio) {
+        for (label, rt) in &runtimes {
+            // Interned keys only under join_all
+            if spec.tasks.contains(&TaskStrategy::JoinAll) {
+                for strategy in &strategies {
+                    group.bench_function(format!("{}{}{}", function, strategy.suffix(), label), |b| {
+                        b.iter(|| match strategy {
+                            KeyStrategy::Strings => rt.block_on(async_hft_orders(&population, &updates)),
+                            KeyStrategy::Interned => rt.block_on(async_interned_hft_orders(&compact, &updates)),
+                        });
+                    });
                 }
             }
-        });
-    });
-    
-    group.bench_function("async_hft_orders", |b| {
-        b.iter(|| {
-            rt.block_on(async {
-                let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
-                
-                // Initial order population
-                let init_tasks: Vec<_> = (0..1000)
-                    .map(|i| {
-                        let order_map = order_map.clone();
-                        async move {
-                            let order_id = format!("ORD_{}", i);
-                            let symbol = format!("STOCK_{}", i % 100);
-                            let client_id = format!("CLIENT_{}", i % 50);
-                            let order = Order::new(i as u64, symbol, client_id);
-                            order_map.insert(order_id, order);
-                        }
+
+            for &tasks in spec.tasks.iter().filter(|&&tasks| tasks != TaskStrategy::JoinAll) {
+                group.bench_function(format!("{}{}{}", function, tasks.suffix(), label), |b| {
+                    b.iter(|| rt.block_on(spawned_hft_orders(&population, &updates, tasks)));
+                });
+            }
+        }
+    }
+
+    group.finish();
+}
+
+// Open-loop load at a fixed target rate. Criterion sees the mean latency
+// from intended send time, the HDR histograms keep the tail.
+fn bench_open_loop(c: &mut Criterion, spec: &ScenarioSpec, orders: usize, rate: Option<u64>, symbols: usize, clients: usize) {
+    let mut group = c.benchmark_group(&spec.name);
+    configure_group(&mut group, &spec.criterion);
+
+    let runtimes = runtimes(spec);
+    let seed = workload_seed();
+    let rate = rate.unwrap_or_else(|| open_loop::target_rate_from_env().unwrap_or_else(|err| panic!("{}", err)));
+    let schedule = Schedule::new(rate);
+    let workload = Arc::new(OrderWorkload::generate(seed, orders, symbols, clients));
+    // Set on the group so --sample-size cannot change it under the batches
+    let samples = measured_samples(&spec.criterion);
+    group.sample_size(samples);
+    let mut latencies = Vec::new();
+
+    if let Some(function) = spec.function(Scenario::Sync) {
+        let scenario = format!("{}/{}/{}", spec.name, function, rate);
+        let mut batches = OpenLoopBatches::new(samples);
+        group.bench_function(BenchmarkId::new(function, rate), |b| {
+            b.iter_custom(|iters| {
+                let mut order_map: HashMap<String, Order> = HashMap::new();
+                batches
+                    .record(|recorder| {
+                        open_loop::run_sync(schedule, iters, recorder, |i| {
+                            open_loop_hashmap_request(&mut order_map, &workload, i)
+                        })
                     })
-                    .collect();
-                
-                join_all(init_tasks).await;
-                
-                // High frequency order updates
-                let update_tasks: Vec<_> = (0..order_updates)
-                    .map(|_| {
-                        let order_map = order_map.clone();
-                        async move {
-                            let mut rng = thread_rng();
-                            let order_id = format!("ORD_{}", rng.gen_range(0..1000));
-                            if let Some(mut order) = order_map.get_mut(&order_id) {
-                                if order.remaining_quantity > 0 {
-                                    let fill_qty = rng.gen_range(1..=order.remaining_quantity);
-                                    order.update_fill(fill_qty);
-                                }
-                            }
-                        }
+                    .total_latency
+            });
+        });
+        latencies.extend(batches.report(&scenario, schedule));
+    }
+
+    if let Some(function) = spec.function(Scenario::DashMap) {
+        let scenario = format!("{}/{}/{}", spec.name, function, rate);
+        let mut batches = OpenLoopBatches::new(samples);
+        group.bench_function(BenchmarkId::new(function, rate), |b| {
+            b.iter_custom(|iters| {
+                let order_map: DashMap<String, Order> = DashMap::new();
+                batches
+                    .record(|recorder| {
+                        open_loop::run_sync(schedule, iters, recorder, |i| {
+                            open_loop_dashmap_request(&order_map, &workload, i)
+                        })
                     })
-                    .collect();
-                
-                join_all(update_tasks).await;
+                    .total_latency
             });
         });
-    });
-    
+        latencies.extend(batches.report(&scenario, schedule));
+    }
+
+    if let Some(function) = spec.function(Scenario::Tokio) {
+        for (label, rt) in &runtimes {
+            let scenario = format!("{}/{}{}/{}", spec.name, function, label, rate);
+            let mut batches = OpenLoopBatches::new(samples);
+            group.bench_function(BenchmarkId::new(format!("{}{}", function, label), rate), |b| {
+                b.iter_custom(|iters| {
+                    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
+                    batches
+                        .record(|recorder| {
+                            rt.block_on(open_loop::run_tokio(schedule, iters, recorder, |i| {
+                                let order_map = order_map.clone();
+                                let workload = workload.clone();
+                                async move {
+                                    open_loop_dashmap_request(&order_map, &workload, i);
+                                }
+                            }))
+                        })
+                        .total_latency
+                });
+            });
+            latencies.extend(batches.report(&scenario, schedule));
+        }
+    }
+
     group.finish();
+
+    save_latencies(&latencies);
 }
 
-fn bench_trillion_scale(c: &mut Criterion) {
-    let mut group = c.benchmark_group("trillion_scale_orders");
-    group.sample_size(10); // Reduce sample size for large benchmarks
-    group.measurement_time(std::time::Duration::from_secs(60)); // Longer measurement time
-    
-    let rt = Runtime::new().unwrap();
-    
-    // Test with different batch sizes to simulate trillion transactions
-    // 1 trillion = 1,000,000,000,000
-    // We'll use smaller representative samples
-    let test_configs = [
-        (100_000, 1000),    // 100M transactions
-        (1_000_000, 100),   // 100M transactions
-        (10_000_000, 10),   // 100M transactions
-    ];
-    
-    for (batch_size, batches) in test_configs.iter() {
-        group.bench_with_input(
-            BenchmarkId::new("sync_trillion", format!("{}x{}", batch_size, batches)),
-            &(*batch_size, *batches),
-            |b, &(batch_size, batches)| {
-                b.iter(|| sync_trillion_transactions(batch_size, batches));
-            },
-        );
-        
-        group.bench_with_input(
-            BenchmarkId::new("async_trillion", format!("{}x{}", batch_size, batches)),
-            &(*batch_size, *batches),
-            |b, &(batch_size, batches)| {
-                b.iter(|| {
-                    rt.block_on(async_trillion_transactions(batch_size, batches));
-                });
-            },
-        );
+// Test with different batch sizes to simulate trillion transactions
+// 1 trillion = 1,000,000,000,000
+// We'll use smaller representative samples
+fn bench_trillion_scale(c: &mut Criterion, spec: &ScenarioSpec, test_configs: &[(usize, usize)], symbols: usize, clients: usize) {
+    let mut group = c.benchmark_group(&spec.name);
+    configure_group(&mut group, &spec.criterion);
+
+    let runtimes = runtimes(spec);
+    let seed = workload_seed();
+
+    for &(batch_size, batches) in test_configs {
+        if let Some(function) = spec.function(Scenario::DashMap) {
+            group.bench_with_input(
+                BenchmarkId::new(function, format!("{}x{}", batch_size, batches)),
+                &(batch_size, batches),
+                |b, &(batch_size, batches)| {
+                    b.iter(|| sync_trillion_transactions(seed, batch_size, batches, symbols, clients));
+                },
+            );
+        }
+
+        if let Some(function) = spec.function(Scenario::Tokio) {
+            for &tasks in &spec.tasks {
+                for (label, rt) in &runtimes {
+                    group.bench_with_input(
+                        BenchmarkId::new(format!("{}{}{}", function, tasks.suffix(), label), format!("{}x{}", batch_size, batches)),
+                        &(batch_size, batches),
+                        |b, &(batch_size, batches)| {
+                            b.iter(|| {
+                                rt.block_on(async_trillion_transactions(seed, batch_size, batches, symbols, clients, tasks));
+                            });
+                        },
+                    );
+                }
+            }
+        }
     }
-    
+
     group.finish();
 }
 
-criterion_group!(
-    benches,
-    bench_sync_operations,
-    bench_async_operations,
-    bench_order_flattening,
-    bench_hft_simulation,
-    bench_trillion_scale
-);
-criterion_main!(benches);
\ No newline at end of file
+// Every group comes from the scenario file (HFT_SCENARIO_FILE, scenarios.json by default)
+fn bench_scenarios(c: &mut Criterion) {
+    let file = ScenarioFile::from_env().unwrap_or_else(|err| panic!("could not load scenario file: {}", err));
+
+    for spec in &file.scenarios {
+        match &spec.workload {
+            WorkloadSpec::OrderOperations { sizes, symbols, clients } => {
+                bench_order_operations(c, spec, sizes, *symbols, *clients);
+            }
+            WorkloadSpec::OrderFlattening { exchanges, orders_per_exchange, symbols, clients } => {
+                bench_order_flattening(c, spec, exchanges, orders_per_exchange, *symbols, *clients);
+            }
+            // Fixed-point tick prices as level keys
+            WorkloadSpec::OrderBook { sizes, prices: PriceLevels::Ticks, symbols, clients } => {
+                bench_order_books::<Price>(c, spec, sizes, *symbols, *clients);
+            }
+            WorkloadSpec::OrderBook { sizes, prices: PriceLevels::Float, symbols, clients } => {
+                bench_order_books::<FloatPrice>(c, spec, sizes, *symbols, *clients);
+            }
+            WorkloadSpec::StopTriggers { sizes, trades, clients } => {
+                bench_stop_triggers(c, spec, sizes, *trades, *clients);
+            }
+            WorkloadSpec::HftSimulation { orders, updates, symbols, clients } => {
+                bench_hft_simulation(c, spec, *orders, *updates, *symbols, *clients);
+            }
+            WorkloadSpec::OpenLoop { orders, rate, symbols, clients } => {
+                bench_open_loop(c, spec, *orders, *rate, *symbols, *clients);
+            }
+            WorkloadSpec::TrillionScale { batches, symbols, clients } => {
+                bench_trillion_scale(c, spec, batches, *symbols, *clients);
+            }
+        }
+    }
+}
+
+criterion_group!(benches, bench_scenarios);
+criterion_main!(benches);
@@ -1,40 +1,229 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
-use benchmark_async_vs_sync::simple_plotter::{SimplePlotter, BenchmarkResult};
+use std::sync::OnceLock;
+use benchmark_async_vs_sync::simple_plotter::{SimplePlotter, BenchmarkResult, ReportFormat};
+use benchmark_async_vs_sync::interner::KeyStrategy;
+use benchmark_async_vs_sync::scenario_file::{RuntimeSettings, ScenarioFile, ScenarioSpec, WorkloadSpec, RUNTIME_LABEL_SEPARATOR};
+use benchmark_async_vs_sync::scenarios::TaskStrategy;
+use benchmark_async_vs_sync::compare::{self, ComparisonReport};
+use benchmark_async_vs_sync::history::{HistoryStore, RunRecord};
+use benchmark_async_vs_sync::environment::Environment;
+use benchmark_async_vs_sync::{latency, workload};
+#[cfg(feature = "charts")]
+use benchmark_async_vs_sync::plotter::BenchmarkPlotter;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use regex::Regex;
+
+/// Number of recorded runs shown in the trend report.
+const TREND_RUNS: usize = 10;
+
+const DEFAULT_CRITERION_DIR: &str = "target/criterion";
+const DEFAULT_OUTPUT_DIR: &str = "hft_benchmark_report";
+
+fn filter_arg() -> Arg<'static> {
+    Arg::new("filter")
+        .short('f')
+        .long("filter")
+        .value_name("REGEX")
+        .action(ArgAction::Set)
+        .help("Only keep benchmarks whose full name (e.g. sync_order_operations/concurrent/1000) matches REGEX")
+}
+
+fn output_arg() -> Arg<'static> {
+    Arg::new("output")
+        .short('o')
+        .long("output")
+        .value_name("DIR")
+        .action(ArgAction::Set)
+        .default_value(DEFAULT_OUTPUT_DIR)
+        .help("Directory the report files are written to")
+}
+
+fn report_args() -> Vec<Arg<'static>> {
+    vec![
+        Arg::new("input")
+            .short('i')
+            .long("input")
+            .value_name("PATH")
+            .action(ArgAction::Set)
+            .default_value(DEFAULT_CRITERION_DIR)
+            .help("Criterion output directory, or a benchmark_results.csv/.json export"),
+        output_arg(),
+        filter_arg(),
+        Arg::new("format")
+            .long("format")
+            .value_name("LIST")
+            .action(ArgAction::Append)
+            .use_value_delimiter(true)
+            .value_parser(|value: &str| {
+                ReportFormat::parse(value).ok_or_else(|| format!("unknown format '{}' (expected csv, json, md, html or txt)", value))
+            })
+            .default_value("csv,json,md,html,txt")
+            .help("Comma-separated report files to write: csv, json, md, html, txt"),
+        Arg::new("no-sample-fallback")
+            .long("no-sample-fallback")
+            .action(ArgAction::SetTrue)
+            .help("Fail when no results are found instead of reporting built-in sample data"),
+        Arg::new("no-history")
+            .long("no-history")
+            .action(ArgAction::SetTrue)
+            .help("Do not record this run in the benchmark history"),
+    ]
+}
+
+fn cli() -> Command<'static> {
+    Command::new("plot_results")
+        .about("Reports, compares and tracks HFT order processing benchmark results")
+        .args_conflicts_with_subcommands(true)
+        .args(report_args())
+        .subcommand(
+            Command::new("report")
+                .about("Write CSV, JSON, Markdown, HTML and text reports (the default)")
+                .args(report_args()),
+        )
+        .subcommand(
+            Command::new("compare")
+                .about("Compare two result sets and exit with status 1 on any regression or missing benchmark")
+                .arg(
+                    Arg::new("threshold")
+                        .long("threshold")
+                        .value_name("PCT")
+                        .action(ArgAction::Set)
+                        .value_parser(|value: &str| value.trim_end_matches('%').parse::<f64>().map_err(|err| err.to_string()))
+                        .help("Slowdown beyond which a significant change is a regression [default: 5]"),
+                )
+                .arg(
+                    Arg::new("baseline")
+                        .long("baseline")
+                        .value_name("NAME")
+                        .action(ArgAction::Set)
+                        .default_value("base")
+                        .help("Criterion baseline compared against new/ when no files are given"),
+                )
+                .arg(
+                    Arg::new("input")
+                        .short('i')
+                        .long("input")
+                        .value_name("DIR")
+                        .action(ArgAction::Set)
+                        .default_value(DEFAULT_CRITERION_DIR)
+                        .help("Criterion output directory used when no files are given"),
+                )
+                .arg(output_arg())
+                .arg(filter_arg())
+                .arg(
+                    Arg::new("files")
+                        .value_name("BASE NEW")
+                        .action(ArgAction::Append)
+                        .max_values(2)
+                        .help("Two CSV or JSON exports to compare instead of Criterion runs"),
+                ),
+        )
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = cli().get_matches();
+    match matches.subcommand() {
+        Some(("compare", args)) => run_compare(args),
+        Some(("report", args)) => run_report(args),
+        _ => run_report(&matches),
+    }
+}
+
+fn string_arg<'a>(args: &'a ArgMatches, name: &str) -> &'a str {
+    args.get_one::<String>(name).map(String::as_str).expect("argument has a default")
+}
+
+fn parse_filter(args: &ArgMatches) -> Result<Option<Regex>, Box<dyn std::error::Error>> {
+    match args.get_one::<String>("filter") {
+        Some(pattern) => Ok(Some(Regex::new(pattern)?)),
+        None => Ok(None),
+    }
+}
+
+fn run_report(args: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 HFT Benchmark Results Plotter");
     
+    let input = string_arg(args, "input");
+    let output_dir = string_arg(args, "output");
+    let filter = parse_filter(args)?;
+    let formats: Vec<ReportFormat> = args.get_many::<ReportFormat>("format").expect("argument has a default").copied().collect();
+    
     let mut plotter = SimplePlotter::new();
+    let mut from_criterion = false;
     
-    // Try to parse criterion results from target/criterion directory
-    let criterion_dir = "target/criterion";
-    if Path::new(criterion_dir).exists() {
+    // A results file from an earlier export, otherwise a Criterion directory
+    if input.ends_with(".json") || input.ends_with(".csv") {
+        println!("📄 Loading results from {}", input);
+        plotter = load_results_file(input)?;
+        println!("✅ Loaded {} benchmark results", plotter.results.len());
+    } else if Path::new(input).exists() {
         println!("📁 Found criterion results directory, parsing...");
-        if let Ok(results) = parse_criterion_results(criterion_dir) {
+        let results = parse_criterion_results(input).unwrap_or_else(|err| {
+            println!("⚠️  Could not parse {}: {}", input, err);
+            Vec::new()
+        });
+        if !results.is_empty() {
+            from_criterion = true;
             for result in results {
                 plotter.add_result(result);
             }
             println!("✅ Parsed {} benchmark results from criterion data", plotter.results.len());
-        } else {
-            println!("⚠️  Could not parse criterion results, using sample data");
-            plotter.add_sample_results();
+            if let Some(seed) = workload::load_recorded_seed(Path::new(input)) {
+                println!("🎲 Workload seed: {}", seed);
+                plotter.set_seed(seed);
+            }
+            if let Some(latencies) = latency::load_summaries(Path::new(input)) {
+                println!("⏱️  Loaded latency percentiles for {} scenario operations", latencies.len());
+                plotter.add_latency_summaries(latencies);
+            }
+            if let Some(environment) = Environment::load(Path::new(input)) {
+                println!("🖥️  Benchmarks ran on {} ({} cores)", environment.cpu_model.as_deref().unwrap_or("unknown CPU"), environment.logical_cores);
+                plotter.set_environment(environment);
+            }
         }
-    } else {
-        println!("⚠️  No criterion results found, using sample data");
+    }
+    
+    if plotter.results.is_empty() {
+        if args.get_flag("no-sample-fallback") {
+            return Err(format!("no benchmark results found in {}", input).into());
+        }
+        println!("⚠️  No benchmark results found in {}, using sample data", input);
         println!("💡 Run 'cargo bench' first to generate real benchmark data");
         plotter.add_sample_results();
     }
     
-    // Generate the complete report
-    let output_dir = "hft_benchmark_report";
-    plotter.generate_complete_report(output_dir)?;
+    if let Some(filter) = &filter {
+        plotter.results.retain(|result| filter.is_match(&result.name));
+        plotter.latencies.retain(|summary| filter.is_match(&summary.scenario));
+        if plotter.results.is_empty() {
+            return Err(format!("no benchmark results match --filter '{}'", filter).into());
+        }
+        println!("🔎 {} results match '{}'", plotter.results.len(), filter);
+    }
+    
+    plotter.generate_report(output_dir, &formats)?;
+    
+    #[cfg(feature = "charts")]
+    BenchmarkPlotter::from_results(plotter.results.clone()).generate_charts(output_dir)?;
     
     println!("\n🎉 Benchmark visualization complete!");
-    println!("📊 Open {}/benchmark_report.html to view the interactive report", output_dir);
-    println!("📈 Charts saved as PNG files in {} directory", output_dir);
-    println!("📄 Raw data exported to {}/benchmark_results.csv", output_dir);
+    if formats.contains(&ReportFormat::Html) {
+        println!("📊 Open {}/benchmark_report.html to view the report and charts", output_dir);
+    }
+    if cfg!(feature = "charts") {
+        println!("📈 Charts saved as PNG files in {} directory", output_dir);
+    }
+    if formats.contains(&ReportFormat::Csv) {
+        println!("📄 Raw data exported to {}/benchmark_results.csv", output_dir);
+    }
+    
+    // Sample data is not a real run, and a filtered one is not a whole run;
+    // keep both out of the history
+    if from_criterion && filter.is_none() && !args.get_flag("no-history") {
+        record_history(&plotter, output_dir)?;
+    }
     
     // Print quick summary
     print_performance_summary(&plotter)?;
@@ -42,70 +231,338 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn record_history(plotter: &SimplePlotter, output_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let store = HistoryStore::from_env();
+    let record = RunRecord::capture(plotter.results.clone(), plotter.seed, plotter.environment.clone());
+    if store.append(&record)? {
+        println!("🗃️  Run recorded in {}", store.path().display());
+    } else {
+        println!("🗃️  Run already recorded in {}", store.path().display());
+    }
+    
+    let history = store.recent(TREND_RUNS)?;
+    if history.len() >= 2 {
+        plotter.print_trend_report(&history);
+        plotter.generate_trend_report(&history, &format!("{}/trend_report.md", output_dir))?;
+    }
+    Ok(())
+}
+
+// Without files, compares each Criterion benchmark's baseline run (base/ by
+// default) against new/. Exits with status 1 on any regression, or when a
+// baseline benchmark has no counterpart in the new results.
+fn run_compare(args: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🚀 HFT Benchmark Comparison");
+    
+    let threshold_pct = args.get_one::<f64>("threshold").copied().unwrap_or(compare::DEFAULT_THRESHOLD_PCT);
+    let baseline = string_arg(args, "baseline");
+    let criterion_dir = string_arg(args, "input");
+    let output_dir = string_arg(args, "output");
+    let filter = parse_filter(args)?;
+    let files: Vec<&String> = args.get_many::<String>("files").map(Iterator::collect).unwrap_or_default();
+    
+    let (mut base, mut new, environments) = match files.as_slice() {
+        [] => {
+            println!("📁 Comparing Criterion '{}' against 'new' in {}", baseline, criterion_dir);
+            (
+                parse_criterion_runs(criterion_dir, RunSelection::Only(baseline))?,
+                parse_criterion_runs(criterion_dir, RunSelection::Only("new"))?,
+                // Criterion keeps one environment file, not one per baseline
+                (None, None),
+            )
+        }
+        [base_path, new_path] => {
+            println!("📄 Comparing {} against {}", base_path, new_path);
+            let base = load_results_file(base_path)?;
+            let new = load_results_file(new_path)?;
+            (base.results, new.results, (base.environment, new.environment))
+        }
+        _ => return Err("compare takes either no files or exactly two CSV or JSON files (base, new)".into()),
+    };
+    
+    if let Some(filter) = &filter {
+        base.retain(|result| filter.is_match(&result.name));
+        new.retain(|result| filter.is_match(&result.name));
+    }
+    
+    if base.is_empty() || new.is_empty() {
+        return Err("nothing to compare: one of the result sets is empty".into());
+    }
+    
+    let report = ComparisonReport::new(&base, &new, threshold_pct)
+        .with_environments(environments.0.as_ref(), environments.1.as_ref());
+    report.print_summary();
+    
+    fs::create_dir_all(output_dir)?;
+    report.write_markdown(&format!("{}/comparison.md", output_dir))?;
+    
+    if !report.passes() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+// Results, seed and environment from a CSV or JSON export
+fn load_results_file(path: &str) -> Result<SimplePlotter, Box<dyn std::error::Error>> {
+    if path.ends_with(".json") {
+        SimplePlotter::import_json(path)
+    } else {
+        SimplePlotter::import_csv(path)
+    }
+}
+
 fn parse_criterion_results(criterion_dir: &str) -> Result<Vec<BenchmarkResult>, Box<dyn std::error::Error>> {
+    parse_criterion_runs(criterion_dir, RunSelection::Latest)
+}
+
+// Which stored run of each benchmark to load
+#[derive(Debug, Clone, Copy)]
+enum RunSelection<'a> {
+    /// new/, falling back to base/, with base/ attached as the baseline
+    Latest,
+    /// Exactly this run directory, e.g. new/, base/ or a named baseline
+    Only(&'a str),
+}
+
+fn parse_criterion_runs(criterion_dir: &str, selection: RunSelection) -> Result<Vec<BenchmarkResult>, Box<dyn std::error::Error>> {
     let mut results = Vec::new();
+    collect_criterion_results(Path::new(criterion_dir), Path::new(criterion_dir), selection, &mut results)?;
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(results)
+}
+
+// Criterion nests results as <group>/<function>/<param>/{new,base,...}; any
+// directory holding a run with a benchmark.json is a benchmark, everything
+// else is walked further. The report/ directories only contain HTML.
+fn collect_criterion_results(
+    root: &Path,
+    dir: &Path,
+    selection: RunSelection,
+    results: &mut Vec<BenchmarkResult>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if is_benchmark_dir(dir)? {
+        if let Some(result) = load_benchmark(root, dir, selection) {
+            results.push(result);
+        }
+        return Ok(());
+    }
     
-    // Walk through criterion directory structure
-    for entry in fs::read_dir(criterion_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        if path.is_dir() {
-            let dir_name = path.file_name().unwrap().to_string_lossy();
-            
-            // Look for estimates.json files in each benchmark directory
-            let estimates_path = path.join("base").join("estimates.json");
-            if estimates_path.exists() {
-                if let Ok(estimate_data) = fs::read_to_string(&estimates_path) {
-                    if let Ok(estimate_json) = serde_json::from_str::<serde_json::Value>(&estimate_data) {
-                        if let Some(mean_estimate) = estimate_json.get("mean") {
-                            if let Some(point_estimate) = mean_estimate.get("point_estimate") {
-                                if let Some(time_ns) = point_estimate.as_f64() {
-                                    // Parse benchmark name and extract info
-                                    let (operation_type, data_size) = parse_benchmark_name(&dir_name);
-                                    
-                                    let result = BenchmarkResult::new(
-                                        dir_name.to_string(),
-                                        operation_type,
-                                        data_size,
-                                        time_ns,
-                                    );
-                                    results.push(result);
-                                }
-                            }
-                        }
-                    }
-                }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() && path.file_name().is_some_and(|name| name != "report") {
+            collect_criterion_results(root, &path, selection, results)?;
+        }
+    }
+    
+    Ok(())
+}
+
+fn is_benchmark_dir(dir: &Path) -> Result<bool, Box<dyn std::error::Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() && path.join("benchmark.json").exists() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn load_benchmark(root: &Path, dir: &Path, selection: RunSelection) -> Option<BenchmarkResult> {
+    let new_dir = dir.join("new");
+    let base_dir = dir.join("base");
+    
+    let (run_dir, estimates, base_time_ns) = match selection {
+        RunSelection::Latest => {
+            // Prefer the latest run; a directory with only base/ still has a result
+            let base_estimates = read_estimates(&base_dir);
+            match (read_estimates(&new_dir), base_estimates) {
+                (Some(estimates), _) => (new_dir.clone(), estimates, base_estimates.map(|base| base.mean)),
+                (None, Some(estimates)) => (base_dir.clone(), estimates, None),
+                (None, None) => return None,
             }
         }
+        RunSelection::Only(run) => {
+            let run_dir = dir.join(run);
+            let estimates = read_estimates(&run_dir)?;
+            (run_dir, estimates, None)
+        }
+    };
+    
+    let benchmark_id = read_benchmark_id(&run_dir)
+        .or_else(|| read_benchmark_id(&new_dir))
+        .or_else(|| read_benchmark_id(&base_dir))
+        .unwrap_or_else(|| {
+            // Directory names are sanitized ids, close enough as a fallback
+            dir.strip_prefix(root)
+                .unwrap_or(dir)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        });
+    
+    let (operation_type, data_size) = parse_benchmark_name(&benchmark_id);
+    let mut result = BenchmarkResult::new(benchmark_id, operation_type, data_size, estimates.mean)
+        .with_uncertainty(estimates.median, estimates.std_dev, estimates.mean_lower, estimates.mean_upper)
+        .with_samples(read_samples(&run_dir).unwrap_or_default());
+    result.base_time_ns = base_time_ns;
+    Some(result)
+}
+
+// Full group/function/param id as Criterion recorded it
+fn read_benchmark_id(run_dir: &Path) -> Option<String> {
+    let data = fs::read_to_string(run_dir.join("benchmark.json")).ok()?;
+    let benchmark: serde_json::Value = serde_json::from_str(&data).ok()?;
+    
+    if let Some(full_id) = benchmark.get("full_id").and_then(|id| id.as_str()) {
+        return Some(full_id.to_string());
     }
     
-    Ok(results)
+    let parts: Vec<&str> = ["group_id", "function_id", "value_str"]
+        .iter()
+        .filter_map(|key| benchmark.get(*key).and_then(|value| value.as_str()))
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("/"))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Estimates {
+    mean: f64,
+    mean_lower: f64,
+    mean_upper: f64,
+    median: f64,
+    std_dev: f64,
+}
+
+fn read_estimates(run_dir: &Path) -> Option<Estimates> {
+    let data = fs::read_to_string(run_dir.join("estimates.json")).ok()?;
+    let estimates: serde_json::Value = serde_json::from_str(&data).ok()?;
+    
+    let point = |name: &str| estimates.get(name)?.get("point_estimate")?.as_f64();
+    let bound = |name: &str, bound: &str| {
+        estimates.get(name)?.get("confidence_interval")?.get(bound)?.as_f64()
+    };
+    
+    let mean = point("mean")?;
+    Some(Estimates {
+        mean,
+        mean_lower: bound("mean", "lower_bound").unwrap_or(mean),
+        mean_upper: bound("mean", "upper_bound").unwrap_or(mean),
+        median: point("median").unwrap_or(mean),
+        std_dev: point("std_dev").unwrap_or(0.0),
+    })
+}
+
+// sample.json holds total time per sample; divide by its iteration count
+fn read_samples(run_dir: &Path) -> Option<Vec<f64>> {
+    let data = fs::read_to_string(run_dir.join("sample.json")).ok()?;
+    let sample: serde_json::Value = serde_json::from_str(&data).ok()?;
+    let iters = sample.get("iters")?.as_array()?;
+    let times = sample.get("times")?.as_array()?;
+    
+    Some(
+        iters
+            .iter()
+            .zip(times)
+            .filter_map(|(iters, time)| Some(time.as_f64()? / iters.as_f64()?))
+            .collect(),
+    )
+}
+
+// Scenario files benchmark groups are looked up in: the one the bench reads
+// (HFT_SCENARIO_FILE or scenarios.json), then the built-in matrix
+fn scenario_files() -> &'static [ScenarioFile] {
+    static FILES: OnceLock<Vec<ScenarioFile>> = OnceLock::new();
+    FILES.get_or_init(|| ScenarioFile::from_env().into_iter().chain([ScenarioFile::builtin()]).collect())
+}
+
+fn scenario_spec(group: &str) -> Option<&'static ScenarioSpec> {
+    scenario_files().iter().find_map(|file| file.spec(group))
+}
+
+// Variant of a function from a group no scenario file describes, from the
+// prefixes the benchmark function names use
+fn function_variant(function: &str) -> Option<&'static str> {
+    if function.starts_with("async") || function.starts_with("tokio") {
+        Some("Async")
+    } else if function.starts_with("concurrent") || function.starts_with("dashmap") {
+        Some("Concurrent")
+    } else if function.starts_with("sync") || function.starts_with("single_threaded") {
+        Some("Sync")
+    } else {
+        None
+    }
+}
+
+fn parse_data_size(parameter: &str) -> Option<usize> {
+    match parameter.split_once('x') {
+        Some((a, b)) => a.parse::<usize>().ok()?.checked_mul(b.parse().ok()?),
+        None => parameter.parse().ok(),
+    }
 }
 
 fn parse_benchmark_name(name: &str) -> (String, usize) {
     // Parse benchmark names like "sync_order_operations/single_threaded/100"
     let parts: Vec<&str> = name.split('/').collect();
+    let group = parts[0];
+    let spec = scenario_spec(group);
     
     let operation_type = if parts.len() >= 2 {
-        match (parts[0], parts[1]) {
-            ("sync_order_operations", "single_threaded") => "Sync Single".to_string(),
-            ("sync_order_operations", "concurrent") => "Sync Concurrent".to_string(),
-            ("async_order_operations", "async") => "Async".to_string(),
-            ("order_flattening", "sync") => "Flatten Sync".to_string(),
-            ("order_flattening", "async") => "Flatten Async".to_string(),
-            ("hft_order_simulation", _) => "HFT Simulation".to_string(),
-            ("trillion_scale_orders", _) => "Trillion Scale".to_string(),
-            _ => format!("{} {}", parts[0], parts.get(1).unwrap_or(&"")),
+        // Tokio runtime variants end in "@<label>", e.g. "async_interned@mt4"
+        let (function, runtime_label) = match parts[1].split_once(RUNTIME_LABEL_SEPARATOR) {
+            Some((function, label)) => (function, Some(label)),
+            None => (parts[1], None),
+        };
+        // Interned key strategy variants share the base name plus a suffix
+        let interned_suffix = KeyStrategy::Interned.suffix();
+        let (function, interned) = match function.strip_suffix(interned_suffix) {
+            Some(base) => (base, true),
+            None => (function, false),
+        };
+        // So do the spawned task variants, e.g. "async_spawn" or "async_chunked64"
+        let (function, tasks) = TaskStrategy::strip_suffix(function);
+        // The scenario's workload and the execution the function belongs to
+        // name the type; groups no scenario file knows fall back to the
+        // function name's sync_/dashmap/async_ prefix
+        let execution = spec.and_then(|spec| Some((spec, spec.workload.execution_of(function)?)));
+        let operation_type = match (execution, function_variant(function)) {
+            (Some((spec, execution)), _) => spec.workload.operation_type(execution),
+            (None, Some(variant)) => format!("{} {}", group, variant),
+            (None, None) => format!("{} {}", group, function),
+        };
+        let operation_type = if interned {
+            format!("{} Interned", operation_type)
+        } else {
+            operation_type
+        };
+        let operation_type = match tasks {
+            TaskStrategy::JoinAll => operation_type,
+            tasks => format!("{} {}", operation_type, tasks.operation_name()),
+        };
+        match runtime_label {
+            Some(label) => match RuntimeSettings::parse(label) {
+                Some(runtime) => runtime.operation_type(&operation_type),
+                None => format!("{} [{}]", operation_type, label),
+            },
+            None => operation_type,
         }
     } else {
-        parts[0].to_string()
+        group.to_string()
     };
     
-    let data_size = if let Some(last_part) = parts.last() {
-        last_part.parse::<usize>().unwrap_or(1)
-    } else {
-        1
+    // Open-loop ids carry the target rate and report latency per order;
+    // "AxB" parameters (exchanges x orders, batch size x batches) count
+    // A * B orders, and the HFT simulation has no parameter at all
+    let parameter = if parts.len() >= 3 { parts.last() } else { None };
+    let data_size = match (spec.map(|spec| &spec.workload), parameter) {
+        (Some(WorkloadSpec::OpenLoop { .. }), _) => 1,
+        (_, Some(parameter)) => parse_data_size(parameter).unwrap_or(1),
+        (Some(WorkloadSpec::HftSimulation { orders, .. }), None) => *orders,
+        (_, None) => 1,
     };
     
     (operation_type, data_size)
@@ -119,22 +576,30 @@ fn print_performance_summary(plotter: &SimplePlotter) -> Result<(), Box<dyn std:
     let mut grouped: HashMap<String, Vec<&BenchmarkResult>> = HashMap::new();
     for result in &plotter.results {
         grouped.entry(result.operation_type.clone())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(result);
     }
     
     for (op_type, mut results) in grouped {
-        results.sort_by(|a, b| a.data_size.cmp(&b.data_size));
+        results.sort_by_key(|r| r.data_size);
         
         println!("\n🔸 {}", op_type);
         for result in results {
             let per_order_ns = result.time_ns / result.data_size as f64;
-            println!("  {} orders: {:.1}µs ({:.0}ns/order, {:.0}M ops/sec)", 
+            println!("  {} orders: {:.1}µs ±{:.1}% ({:.0}ns/order, {:.0}M ops/sec)", 
                 result.data_size,
                 result.time_us,
+                result.relative_error_pct(),
                 per_order_ns,
                 result.throughput_ops_per_sec / 1_000_000.0
             );
+            // Criterion overwrites base with new unless run with --baseline
+            if let Some(base_time_ns) = result.base_time_ns.filter(|&base| base != result.time_ns) {
+                println!("    vs base: {:.1}µs ({:+.1}%)",
+                    base_time_ns / 1_000.0,
+                    (result.time_ns - base_time_ns) / base_time_ns * 100.0
+                );
+            }
         }
     }
     
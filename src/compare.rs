@@ -0,0 +1,372 @@
+use crate::environment::{self, Environment};
+use crate::simple_plotter::{markdown_cell, BenchmarkResult};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+
+/// Change beyond which a significant slowdown counts as a regression.
+pub const DEFAULT_THRESHOLD_PCT: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Regression,
+    Improvement,
+    /// Within the threshold, or not distinguishable from noise.
+    Unchanged,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Verdict::Regression => "regression",
+            Verdict::Improvement => "improvement",
+            Verdict::Unchanged => "unchanged",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// One benchmark present in both result sets.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub name: String,
+    pub operation_type: String,
+    pub data_size: usize,
+    pub base_ns: f64,
+    pub new_ns: f64,
+    pub change_pct: f64,
+    /// The confidence intervals of the two means do not overlap.
+    pub significant: bool,
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    pub fn new(base: &BenchmarkResult, new: &BenchmarkResult, threshold_pct: f64) -> Self {
+        let change_pct = (new.time_ns - base.time_ns) / base.time_ns * 100.0;
+        // Without recorded intervals (e.g. old CSVs) both bounds equal the
+        // mean, so any difference is treated as significant
+        let significant = new.ci_lower_ns > base.ci_upper_ns || new.ci_upper_ns < base.ci_lower_ns;
+
+        let verdict = if significant && change_pct > threshold_pct {
+            Verdict::Regression
+        } else if significant && change_pct < -threshold_pct {
+            Verdict::Improvement
+        } else {
+            Verdict::Unchanged
+        };
+
+        Self {
+            name: display_name(&new.name, new.data_size),
+            operation_type: new.operation_type.clone(),
+            data_size: new.data_size,
+            base_ns: base.time_ns,
+            new_ns: new.time_ns,
+            change_pct,
+            significant,
+            verdict,
+        }
+    }
+}
+
+/// What identifies a benchmark across result sets. Criterion names usually
+/// end in the size, but results imported from elsewhere may not.
+type BenchmarkKey<'a> = (&'a str, &'a str, usize);
+
+fn key(result: &BenchmarkResult) -> BenchmarkKey<'_> {
+    (&result.name, &result.operation_type, result.data_size)
+}
+
+/// The benchmark name, with the size appended when the name leaves it out.
+fn display_name(name: &str, data_size: usize) -> String {
+    let size = data_size.to_string();
+    match name.rsplit_once('/') {
+        Some((_, last)) if last == size => name.to_string(),
+        _ => format!("{} ({})", name, size),
+    }
+}
+
+/// Names of the benchmarks in `results` that `other` does not have.
+fn unmatched(results: &HashMap<BenchmarkKey, &BenchmarkResult>, other: &HashMap<BenchmarkKey, &BenchmarkResult>) -> Vec<String> {
+    let mut keys: Vec<&BenchmarkKey> = results.keys().filter(|key| !other.contains_key(*key)).collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|&(name, _, data_size)| display_name(name, data_size))
+        .collect()
+}
+
+/// Benchmark-by-benchmark comparison of two result sets, matched by name,
+/// operation type and size.
+#[derive(Debug, Clone)]
+pub struct ComparisonReport {
+    pub threshold_pct: f64,
+    pub comparisons: Vec<Comparison>,
+    pub only_in_base: Vec<String>,
+    pub only_in_new: Vec<String>,
+    /// Ways in which the two result sets were produced differently.
+    pub environment_warnings: Vec<String>,
+}
+
+impl ComparisonReport {
+    pub fn new(base: &[BenchmarkResult], new: &[BenchmarkResult], threshold_pct: f64) -> Self {
+        let base_by_key: HashMap<BenchmarkKey, &BenchmarkResult> =
+            base.iter().map(|result| (key(result), result)).collect();
+        let new_by_key: HashMap<BenchmarkKey, &BenchmarkResult> =
+            new.iter().map(|result| (key(result), result)).collect();
+
+        let mut matched: Vec<(&BenchmarkResult, &BenchmarkResult)> = new_by_key
+            .iter()
+            .filter_map(|(key, result)| Some((*base_by_key.get(key)?, *result)))
+            .collect();
+        matched.sort_by(|(_, a), (_, b)| key(a).cmp(&key(b)));
+        let comparisons = matched
+            .into_iter()
+            .map(|(base_result, result)| Comparison::new(base_result, result, threshold_pct))
+            .collect();
+
+        let only_in_base = unmatched(&base_by_key, &new_by_key);
+        let only_in_new = unmatched(&new_by_key, &base_by_key);
+
+        Self {
+            threshold_pct,
+            comparisons,
+            only_in_base,
+            only_in_new,
+            environment_warnings: Vec::new(),
+        }
+    }
+
+    /// Records where the environments of the two result sets differ.
+    pub fn with_environments(mut self, base: Option<&Environment>, new: Option<&Environment>) -> Self {
+        self.environment_warnings = environment::comparison_warnings(base, new);
+        self
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &Comparison> {
+        self.comparisons
+            .iter()
+            .filter(|comparison| comparison.verdict == Verdict::Regression)
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+
+    /// Some baseline benchmark has no counterpart in the new results, or
+    /// nothing matched at all, e.g. after benchmarks were renamed. Such a
+    /// comparison cannot show that nothing regressed.
+    pub fn is_incomplete(&self) -> bool {
+        self.comparisons.is_empty() || !self.only_in_base.is_empty()
+    }
+
+    /// Nothing regressed and every baseline benchmark was compared.
+    pub fn passes(&self) -> bool {
+        !self.has_regressions() && !self.is_incomplete()
+    }
+
+    pub fn print_summary(&self) {
+        println!("\n🔍 BENCHMARK COMPARISON (threshold {:.1}%)", self.threshold_pct);
+        for warning in &self.environment_warnings {
+            println!("⚠️  Different environment: {}", warning);
+        }
+        println!("══════════════════════════════════════════════════════════════════════════════════════════════════");
+        println!("{:<56} │ {:>12} │ {:>12} │ {:>8} │ {:>5} │ {:<11}",
+            "Benchmark", "Base (µs)", "New (µs)", "Change", "Sig.", "Verdict");
+        println!("{}", "─".repeat(118));
+
+        for comparison in &self.comparisons {
+            let marker = match comparison.verdict {
+                Verdict::Regression => "🔴",
+                Verdict::Improvement => "🟢",
+                Verdict::Unchanged => "⚪",
+            };
+            println!("{:<56} │ {:>12.1} │ {:>12.1} │ {:>+7.1}% │ {:>5} │ {} {}",
+                comparison.name,
+                comparison.base_ns / 1_000.0,
+                comparison.new_ns / 1_000.0,
+                comparison.change_pct,
+                if comparison.significant { "yes" } else { "no" },
+                marker,
+                comparison.verdict
+            );
+        }
+
+        for name in &self.only_in_base {
+            println!("➖ {} (missing from new results)", name);
+        }
+        for name in &self.only_in_new {
+            println!("➕ {} (no baseline)", name);
+        }
+
+        let regressions = self.regressions().count();
+        if self.comparisons.is_empty() {
+            println!("\n❌ No benchmark matched the baseline");
+        } else if !self.only_in_base.is_empty() {
+            println!("\n❌ {} baseline benchmark(s) missing from the new results", self.only_in_base.len());
+        }
+        if regressions > 0 {
+            println!("\n❌ {} regression(s) beyond {:.1}%", regressions, self.threshold_pct);
+        } else if !self.is_incomplete() {
+            println!("\n✅ No regressions beyond {:.1}%", self.threshold_pct);
+        }
+    }
+
+    pub fn write_markdown(&self, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::create(output_path)?;
+
+        writeln!(file, "# Benchmark Comparison\n")?;
+        writeln!(file, "Regression threshold: {:.1}%. A change is significant when the 95% confidence intervals of the two means do not overlap.\n", self.threshold_pct)?;
+        if !self.environment_warnings.is_empty() {
+            writeln!(file, "> **Warning:** the two result sets come from different environments, so changes may not be caused by the code.\n>")?;
+            for warning in &self.environment_warnings {
+                writeln!(file, "> - {}", warning)?;
+            }
+            writeln!(file)?;
+        }
+        writeln!(file, "| Benchmark | Base (µs) | New (µs) | Change | Significant | Verdict |")?;
+        writeln!(file, "|-----------|-----------|----------|--------|-------------|---------|")?;
+
+        for comparison in &self.comparisons {
+            writeln!(file, "| {} | {:.1} | {:.1} | {:+.1}% | {} | {} |",
+                markdown_cell(&comparison.name),
+                comparison.base_ns / 1_000.0,
+                comparison.new_ns / 1_000.0,
+                comparison.change_pct,
+                if comparison.significant { "yes" } else { "no" },
+                comparison.verdict
+            )?;
+        }
+
+        if !self.only_in_base.is_empty() || !self.only_in_new.is_empty() {
+            writeln!(file, "\n## Unmatched Benchmarks\n")?;
+            for name in &self.only_in_base {
+                writeln!(file, "- `{}` is missing from the new results", name)?;
+            }
+            for name in &self.only_in_new {
+                writeln!(file, "- `{}` has no baseline", name)?;
+            }
+        }
+
+        println!("📝 Comparison report saved to: {}", output_path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, data_size: usize, time_ns: f64, half_width: f64) -> BenchmarkResult {
+        BenchmarkResult::new(name.to_string(), "Async".to_string(), data_size, time_ns)
+            .with_uncertainty(time_ns, half_width, time_ns - half_width, time_ns + half_width)
+    }
+
+    fn results() -> Vec<BenchmarkResult> {
+        vec![
+            result("async_order_operations/async/100", 100, 50_000.0, 1_000.0),
+            result("async_order_operations/async/1000", 1000, 500_000.0, 5_000.0),
+            // Imported results may leave the size out of the name
+            result("sync_order_operations/single_threaded", 100, 46_500.0, 500.0),
+            result("sync_order_operations/single_threaded", 10000, 5_870_000.0, 20_000.0),
+        ]
+    }
+
+    #[test]
+    fn self_comparison_reports_no_changes() {
+        let results = results();
+        let report = ComparisonReport::new(&results, &results, DEFAULT_THRESHOLD_PCT);
+
+        assert_eq!(report.comparisons.len(), results.len());
+        for comparison in &report.comparisons {
+            assert_eq!(comparison.change_pct, 0.0, "{}", comparison.name);
+            assert_eq!(comparison.verdict, Verdict::Unchanged);
+        }
+        assert!(report.only_in_base.is_empty());
+        assert!(report.only_in_new.is_empty());
+        assert!(!report.has_regressions());
+        assert!(report.passes());
+    }
+
+    #[test]
+    fn matches_results_by_size_when_names_repeat() {
+        let base = results();
+        let new: Vec<BenchmarkResult> = base.iter().rev().cloned().collect();
+        let report = ComparisonReport::new(&base, &new, DEFAULT_THRESHOLD_PCT);
+
+        let names: Vec<&str> = report.comparisons.iter().map(|comparison| comparison.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "async_order_operations/async/100",
+                "async_order_operations/async/1000",
+                "sync_order_operations/single_threaded (100)",
+                "sync_order_operations/single_threaded (10000)",
+            ]
+        );
+        assert!(report.comparisons.iter().all(|comparison| comparison.change_pct == 0.0));
+    }
+
+    #[test]
+    fn verdicts_need_significance_and_the_threshold() {
+        let base = result("group/async/100", 100, 100_000.0, 1_000.0);
+
+        let slower = Comparison::new(&base, &result("group/async/100", 100, 110_000.0, 1_000.0), 5.0);
+        assert!(slower.significant);
+        assert_eq!(slower.verdict, Verdict::Regression);
+
+        let faster = Comparison::new(&base, &result("group/async/100", 100, 90_000.0, 1_000.0), 5.0);
+        assert_eq!(faster.verdict, Verdict::Improvement);
+
+        // Significant, but within the threshold
+        let slightly_slower = Comparison::new(&base, &result("group/async/100", 100, 103_000.0, 1_000.0), 5.0);
+        assert!(slightly_slower.significant);
+        assert_eq!(slightly_slower.verdict, Verdict::Unchanged);
+
+        // Beyond the threshold, but the intervals overlap
+        let noisy = Comparison::new(&base, &result("group/async/100", 100, 110_000.0, 20_000.0), 5.0);
+        assert!(!noisy.significant);
+        assert_eq!(noisy.verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn lists_unmatched_benchmarks() {
+        let base = vec![result("group/async/100", 100, 1.0, 0.0), result("group/sync", 10, 1.0, 0.0)];
+        let new = vec![result("group/async/100", 100, 1.0, 0.0), result("group/async/1000", 1000, 1.0, 0.0)];
+        let report = ComparisonReport::new(&base, &new, DEFAULT_THRESHOLD_PCT);
+
+        assert_eq!(report.comparisons.len(), 1);
+        assert_eq!(report.only_in_base, vec!["group/sync (10)"]);
+        assert_eq!(report.only_in_new, vec!["group/async/1000"]);
+        assert!(report.is_incomplete());
+        assert!(!report.passes());
+    }
+
+    #[test]
+    fn fails_when_nothing_matches() {
+        let base = results();
+        // Relabelled operation types leave every benchmark unmatched
+        let new: Vec<BenchmarkResult> = base
+            .iter()
+            .cloned()
+            .map(|mut result| {
+                result.operation_type.push_str(" [current_thread]");
+                result
+            })
+            .collect();
+        let report = ComparisonReport::new(&base, &new, DEFAULT_THRESHOLD_PCT);
+
+        assert!(report.comparisons.is_empty());
+        assert!(!report.has_regressions());
+        assert!(report.is_incomplete());
+        assert!(!report.passes());
+    }
+
+    #[test]
+    fn new_benchmarks_alone_do_not_fail() {
+        let base = vec![result("group/async/100", 100, 1.0, 0.0)];
+        let new = vec![result("group/async/100", 100, 1.0, 0.0), result("group/async/1000", 1000, 1.0, 0.0)];
+        let report = ComparisonReport::new(&base, &new, DEFAULT_THRESHOLD_PCT);
+
+        assert!(report.passes());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::history::{current_git_commit, current_host};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// File written next to the Criterion output describing the machine the
+/// benchmarks ran on.
+pub const ENVIRONMENT_FILE_NAME: &str = "environment.json";
+
+/// Extension replacing `.csv` for the file that holds the environment of a
+/// CSV export, so the CSV itself stays a plain table.
+pub const CSV_SIDECAR_EXTENSION: &str = "environment.json";
+
+/// Machine, toolchain and build the results were produced with.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Environment {
+    pub host: String,
+    pub os: String,
+    pub arch: String,
+    pub kernel: Option<String>,
+    pub cpu_model: Option<String>,
+    pub logical_cores: usize,
+    pub cpu_governor: Option<String>,
+    pub cpu_max_mhz: Option<u64>,
+    pub tokio_worker_threads: usize,
+    pub rustc_version: Option<String>,
+    pub build_profile: String,
+    pub git_commit: Option<String>,
+}
+
+impl Environment {
+    /// Reads the current machine from `/proc`, `/sys` and the process
+    /// environment. Anything that cannot be read is left empty.
+    pub fn capture() -> Self {
+        let logical_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Self {
+            host: current_host(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            kernel: read_trimmed("/proc/sys/kernel/osrelease"),
+            cpu_model: cpu_model(),
+            logical_cores,
+            cpu_governor: read_trimmed("/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor"),
+            cpu_max_mhz: read_trimmed("/sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq")
+                .and_then(|khz| khz.parse::<u64>().ok())
+                .map(|khz| khz / 1_000),
+            tokio_worker_threads: tokio_worker_threads(logical_cores),
+            rustc_version: rustc_version(),
+            build_profile: if cfg!(debug_assertions) { "debug" } else { "release" }.to_string(),
+            git_commit: current_git_commit(),
+        }
+    }
+
+    /// Labelled values in display order.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let or_unknown = |value: &Option<String>| value.clone().unwrap_or_else(|| "unknown".to_string());
+        vec![
+            ("Host", self.host.clone()),
+            ("OS", format!("{} ({})", self.os, self.arch)),
+            ("Kernel", or_unknown(&self.kernel)),
+            ("CPU", or_unknown(&self.cpu_model)),
+            ("Logical cores", self.logical_cores.to_string()),
+            ("CPU governor", or_unknown(&self.cpu_governor)),
+            ("CPU max frequency", self.cpu_max_mhz.map(|mhz| format!("{} MHz", mhz)).unwrap_or_else(|| "unknown".to_string())),
+            ("Tokio worker threads", self.tokio_worker_threads.to_string()),
+            ("rustc", or_unknown(&self.rustc_version)),
+            ("Build profile", self.build_profile.clone()),
+            ("Git commit", or_unknown(&self.git_commit)),
+        ]
+    }
+
+    /// Fields that differ between two environments, as "label: old → new".
+    /// The git commit is left out since comparing commits is the usual point.
+    pub fn differences(&self, other: &Environment) -> Vec<String> {
+        self.fields()
+            .into_iter()
+            .zip(other.fields())
+            .filter(|((label, old), (_, new))| *label != "Git commit" && old != new)
+            .map(|((label, old), (_, new))| format!("{}: {} → {}", label, old, new))
+            .collect()
+    }
+
+    pub fn record(&self, dir: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(ENVIRONMENT_FILE_NAME), serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn load(dir: &Path) -> Option<Self> {
+        fs::read_to_string(dir.join(ENVIRONMENT_FILE_NAME))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+    }
+
+    /// Writes the sidecar of the CSV export at `csv_path`.
+    pub fn record_for_csv(&self, csv_path: &Path) -> std::io::Result<()> {
+        fs::write(csv_sidecar_path(csv_path), serde_json::to_string_pretty(self)?)
+    }
+
+    /// Environment from the sidecar of the CSV export at `csv_path`, if any.
+    pub fn load_for_csv(csv_path: &Path) -> Option<Self> {
+        fs::read_to_string(csv_sidecar_path(csv_path))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+    }
+}
+
+/// `benchmark_results.environment.json` for `benchmark_results.csv`.
+pub fn csv_sidecar_path(csv_path: &Path) -> PathBuf {
+    csv_path.with_extension(CSV_SIDECAR_EXTENSION)
+}
+
+/// Warnings to show before comparing results from `base` and `new`.
+pub fn comparison_warnings(base: Option<&Environment>, new: Option<&Environment>) -> Vec<String> {
+    match (base, new) {
+        (Some(base), Some(new)) => base.differences(new),
+        (None, None) => Vec::new(),
+        _ => vec!["environment is only recorded for one of the result sets".to_string()],
+    }
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        matches!(key.trim(), "model name" | "Model" | "Hardware")
+            .then(|| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    })
+}
+
+// Same rule as the default multi-thread runtime
+fn tokio_worker_threads(logical_cores: usize) -> usize {
+    std::env::var("TOKIO_WORKER_THREADS")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|&threads| threads > 0)
+        .unwrap_or(logical_cores)
+}
+
+fn rustc_version() -> Option<String> {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn environment() -> Environment {
+        Environment {
+            host: "bench-01".to_string(),
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            cpu_model: Some("Xeon".to_string()),
+            logical_cores: 8,
+            tokio_worker_threads: 8,
+            build_profile: "release".to_string(),
+            git_commit: Some("abc123".to_string()),
+            ..Environment::default()
+        }
+    }
+
+    #[test]
+    fn differences_list_changed_fields_but_not_the_commit() {
+        let base = environment();
+        let new = Environment {
+            logical_cores: 16,
+            cpu_governor: Some("performance".to_string()),
+            git_commit: Some("def456".to_string()),
+            ..environment()
+        };
+
+        assert_eq!(
+            base.differences(&new),
+            vec!["Logical cores: 8 → 16", "CPU governor: unknown → performance"]
+        );
+        assert!(base.differences(&environment()).is_empty());
+    }
+
+    #[test]
+    fn warns_when_only_one_side_has_an_environment() {
+        let environment = environment();
+
+        assert!(comparison_warnings(None, None).is_empty());
+        assert!(comparison_warnings(Some(&environment), Some(&environment)).is_empty());
+        assert_eq!(comparison_warnings(Some(&environment), None).len(), 1);
+        assert_eq!(comparison_warnings(None, Some(&environment)).len(), 1);
+    }
+
+    #[test]
+    fn csv_sidecar_round_trips() {
+        let dir = std::env::temp_dir().join(format!("hft-environment-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let csv_path = dir.join("benchmark_results.csv");
+
+        assert_eq!(csv_sidecar_path(&csv_path), dir.join("benchmark_results.environment.json"));
+        assert_eq!(Environment::load_for_csv(&csv_path), None);
+        environment().record_for_csv(&csv_path).unwrap();
+        assert_eq!(Environment::load_for_csv(&csv_path), Some(environment()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,430 @@
+use crate::simple_plotter::BenchmarkResult;
+use std::collections::BTreeMap;
+
+/// Scalability factors inside this band count as linear scaling.
+const LINEAR_SCALING_BAND: (f64, f64) = (0.8, 1.25);
+
+/// How a benchmark executes, inferred from its operation type label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Variant {
+    Sync,
+    Concurrent,
+    Async,
+}
+
+// Words the variant is read from; the rest of the label names the family
+const VARIANT_WORDS: [&str; 6] = ["Sync", "Single", "Concurrent", "DashMap", "Async", "Tokio"];
+
+// Task strategy words of the spawning async variants, as in "Async Spawn"
+const TASK_WORDS: [&str; 4] = ["Spawn", "JoinSet", "Chunked", "FuturesUnordered"];
+
+// "Book Concurrent" -> ("Book", Concurrent, ""), "Sync Single" -> ("", Sync, ""),
+// "Async Interned" -> ("Interned", Async, ""),
+// "Async Chunked 64 [multi_thread, 4 workers]" -> ("", Async, "Chunked 64, multi_thread, 4 workers")
+//
+// The qualifier keeps the task strategy and runtime apart from the family,
+// so every async variant is compared against the same sync result.
+fn classify(operation_type: &str) -> Option<(String, Variant, String)> {
+    let (label, runtime) = match operation_type.trim_end().strip_suffix(']').and_then(|rest| rest.rsplit_once(" [")) {
+        Some((label, runtime)) => (label, Some(runtime)),
+        None => (operation_type, None),
+    };
+    let words: Vec<&str> = label.split_whitespace().collect();
+    let has = |candidates: &[&str]| words.iter().any(|word| candidates.contains(word));
+
+    let variant = if has(&["Async", "Tokio"]) {
+        Variant::Async
+    } else if has(&["Concurrent", "DashMap"]) {
+        Variant::Concurrent
+    } else if has(&["Sync", "Single"]) {
+        Variant::Sync
+    } else {
+        return None;
+    };
+
+    let mut family = Vec::new();
+    let mut qualifier = Vec::new();
+    let mut rest = words.iter().copied().peekable();
+    while let Some(word) = rest.next() {
+        if VARIANT_WORDS.contains(&word) {
+            continue;
+        }
+        if TASK_WORDS.contains(&word) {
+            match rest.next_if(|size| word == "Chunked" && size.parse::<usize>().is_ok()) {
+                Some(size) => qualifier.push(format!("{} {}", word, size)),
+                None => qualifier.push(word.to_string()),
+            }
+        } else {
+            family.push(word);
+        }
+    }
+    qualifier.extend(runtime.map(str::to_string));
+    Some((family.join(" "), variant, qualifier.join(", ")))
+}
+
+// A variant with its task strategy and runtime qualifier
+type Member = (Variant, String);
+
+// Results by family, then by member
+type Families<'a> = BTreeMap<String, BTreeMap<Member, Vec<&'a BenchmarkResult>>>;
+
+fn families(results: &[BenchmarkResult]) -> Families<'_> {
+    let mut families: Families<'_> = BTreeMap::new();
+    for result in results {
+        if let Some((family, variant, qualifier)) = classify(&result.operation_type) {
+            families
+                .entry(family)
+                .or_default()
+                .entry((variant, qualifier))
+                .or_default()
+                .push(result);
+        }
+    }
+    families
+}
+
+fn qualified(name: &str, qualifier: &str) -> String {
+    if qualifier.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} ({})", name, qualifier)
+    }
+}
+
+/// "sync", "concurrent" or "async" for an operation type label that names
+/// one of them.
+pub(crate) fn variant_name(operation_type: &str) -> Option<&'static str> {
+    classify(operation_type).map(|(_, variant, _)| match variant {
+        Variant::Sync => "sync",
+        Variant::Concurrent => "concurrent",
+        Variant::Async => "async",
+    })
+}
+
+fn family_label(family: &str) -> String {
+    if family.is_empty() {
+        "Order operations".to_string()
+    } else {
+        family.to_string()
+    }
+}
+
+fn intervals_overlap(a: &BenchmarkResult, b: &BenchmarkResult) -> bool {
+    a.ci_lower_ns <= b.ci_upper_ns && b.ci_lower_ns <= a.ci_upper_ns
+}
+
+fn format_range(low: f64, high: f64) -> String {
+    if (high - low).abs() < 0.05 {
+        format!("{:.1}x", low)
+    } else {
+        format!("{:.1}x–{:.1}x", low, high)
+    }
+}
+
+// Compares `slower` against `faster` at every data size both were run with.
+// Only emits a claim when every distinguishable pair points the same way.
+fn pairwise_finding(
+    family: &str,
+    faster: (&[&BenchmarkResult], &str),
+    slower: (&[&BenchmarkResult], &str),
+) -> Option<String> {
+    let (faster_results, slower_results) = (faster.0, slower.0);
+
+    let mut ratios = Vec::new();
+    let mut indistinguishable = 0;
+    for fast in faster_results {
+        let Some(slow) = slower_results.iter().find(|slow| slow.data_size == fast.data_size) else {
+            continue;
+        };
+        if intervals_overlap(fast, slow) {
+            indistinguishable += 1;
+        } else {
+            ratios.push(slow.time_ns / fast.time_ns);
+        }
+    }
+
+    let pairs = ratios.len() + indistinguishable;
+    if pairs == 0 {
+        return None;
+    }
+    let label = family_label(family);
+    if ratios.is_empty() {
+        return Some(format!(
+            "**{}**: {} and {} are within measurement noise of each other at all {} sizes",
+            label, faster.1, slower.1, pairs
+        ));
+    }
+
+    let low = ratios.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = ratios.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let noise_note = if indistinguishable > 0 {
+        format!(", {} more within noise", indistinguishable)
+    } else {
+        String::new()
+    };
+
+    if low > 1.0 {
+        Some(format!(
+            "**{}**: {} is {} faster than {} ({} of {} sizes{})",
+            label, faster.1, format_range(low, high), slower.1, ratios.len(), pairs, noise_note
+        ))
+    } else if high < 1.0 {
+        Some(format!(
+            "**{}**: {} is {} faster than {} ({} of {} sizes{})",
+            label, slower.1, format_range(1.0 / high, 1.0 / low), faster.1, ratios.len(), pairs, noise_note
+        ))
+    } else {
+        Some(format!(
+            "**{}**: no consistent winner between {} and {}; the ratio ranges from {:.2}x to {:.2}x across sizes",
+            label, faster.1, slower.1, low, high
+        ))
+    }
+}
+
+fn latency_range_finding(results: &[BenchmarkResult]) -> Option<String> {
+    let per_order = |result: &BenchmarkResult| result.time_ns / result.data_size as f64;
+    let fastest = results.iter().min_by(|a, b| per_order(a).total_cmp(&per_order(b)))?;
+    let slowest = results.iter().max_by(|a, b| per_order(a).total_cmp(&per_order(b)))?;
+
+    Some(format!(
+        "**Per-order latency** ranges from {:.0}ns ({}, {} orders) to {:.0}ns ({}, {} orders)",
+        per_order(fastest),
+        fastest.operation_type,
+        fastest.data_size,
+        per_order(slowest),
+        slowest.operation_type,
+        slowest.data_size
+    ))
+}
+
+fn scaling_finding(results: &[BenchmarkResult]) -> Option<String> {
+    let mut grouped: BTreeMap<&str, Vec<&BenchmarkResult>> = BTreeMap::new();
+    for result in results {
+        grouped.entry(result.operation_type.as_str()).or_default().push(result);
+    }
+
+    let mut linear = Vec::new();
+    let mut super_linear = Vec::new();
+    let mut sub_linear = Vec::new();
+    for (op_type, mut group) in grouped {
+        group.sort_by_key(|result| result.data_size);
+        if group.len() < 2 {
+            continue;
+        }
+        let (first, last) = (group[0], group[group.len() - 1]);
+        if first.data_size == last.data_size {
+            continue;
+        }
+        let factor = (last.time_ns / first.time_ns) / (last.data_size as f64 / first.data_size as f64);
+        let entry = format!("{} ({:.2})", op_type, factor);
+        if factor < LINEAR_SCALING_BAND.0 {
+            sub_linear.push(entry);
+        } else if factor > LINEAR_SCALING_BAND.1 {
+            super_linear.push(entry);
+        } else {
+            linear.push(entry);
+        }
+    }
+
+    if linear.is_empty() && super_linear.is_empty() && sub_linear.is_empty() {
+        return None;
+    }
+    if super_linear.is_empty() && sub_linear.is_empty() {
+        return Some(format!(
+            "**Throughput** scales linearly with order volume for all {} operation types (scalability factor {:.2}–{:.2})",
+            linear.len(),
+            LINEAR_SCALING_BAND.0,
+            LINEAR_SCALING_BAND.1
+        ));
+    }
+
+    let mut parts = Vec::new();
+    if !linear.is_empty() {
+        parts.push(format!("linear for {}", linear.join(", ")));
+    }
+    if !super_linear.is_empty() {
+        parts.push(format!("super-linear for {}", super_linear.join(", ")));
+    }
+    if !sub_linear.is_empty() {
+        parts.push(format!("sub-linear for {}", sub_linear.join(", ")));
+    }
+    Some(format!("**Scaling** (time ratio / size ratio): {}", parts.join("; ")))
+}
+
+/// Statements about `results` that the data supports, as Markdown bullet
+/// text without the leading `- `.
+///
+/// Sync, concurrent and async variants of the same scenario are paired at
+/// equal `data_size`; pairs whose confidence intervals overlap are reported
+/// as noise instead of as a win for either side. Each async task strategy
+/// and runtime is compared against the sync result on its own.
+pub fn key_findings(results: &[BenchmarkResult]) -> Vec<String> {
+    let mut findings = Vec::new();
+    for (family, members) in &families(results) {
+        let of = |wanted: Variant| members.iter().filter(move |((variant, _), _)| *variant == wanted);
+        for ((_, sync_qualifier), sync) in of(Variant::Sync) {
+            let sync_label = qualified("sync", sync_qualifier);
+            for ((_, qualifier), async_results) in of(Variant::Async) {
+                let async_label = qualified("async", qualifier);
+                findings.extend(pairwise_finding(family, (sync, &sync_label), (async_results, &async_label)));
+            }
+            let sync_label = qualified("single-threaded sync", sync_qualifier);
+            for ((_, qualifier), concurrent) in of(Variant::Concurrent) {
+                let concurrent_label = qualified("concurrent (DashMap)", qualifier);
+                findings.extend(pairwise_finding(family, (sync, &sync_label), (concurrent, &concurrent_label)));
+            }
+        }
+    }
+    findings.extend(latency_range_finding(results));
+    findings.extend(scaling_finding(results));
+    findings
+}
+
+fn variant_label(variant: Variant) -> &'static str {
+    match variant {
+        Variant::Sync => "single-threaded sync",
+        Variant::Concurrent => "concurrent (DashMap)",
+        Variant::Async => "async",
+    }
+}
+
+// The member that beats every other member of the family at each size,
+// or `None` for the sizes where the fastest is within noise of another
+fn winners_by_size<'a>(
+    members: &'a BTreeMap<Member, Vec<&BenchmarkResult>>,
+) -> BTreeMap<usize, Option<&'a Member>> {
+    let mut by_size: BTreeMap<usize, Vec<(&Member, &BenchmarkResult)>> = BTreeMap::new();
+    for (member, results) in members {
+        for &result in results {
+            by_size.entry(result.data_size).or_default().push((member, result));
+        }
+    }
+
+    by_size
+        .into_iter()
+        .filter(|(_, entries)| entries.len() >= 2)
+        .map(|(size, entries)| {
+            let (member, fastest) = *entries
+                .iter()
+                .min_by(|a, b| a.1.time_ns.total_cmp(&b.1.time_ns))
+                .expect("at least two entries");
+            let clear = entries
+                .iter()
+                .filter(|(other, _)| *other != member)
+                .all(|(_, other)| !intervals_overlap(fastest, other));
+            (size, clear.then_some(member))
+        })
+        .collect()
+}
+
+/// Which variant to use for each scenario, as Markdown bullet text like
+/// [`key_findings`]. Only variants that were measurably fastest are
+/// recommended.
+pub fn recommendations(results: &[BenchmarkResult]) -> Vec<String> {
+    let mut recommendations = Vec::new();
+    for (family, members) in &families(results) {
+        let winners = winners_by_size(members);
+        if winners.is_empty() {
+            continue;
+        }
+        let label = family_label(family);
+        let member_label = |(variant, qualifier): &Member| qualified(variant_label(*variant), qualifier);
+        let clear: Vec<(usize, &Member)> = winners
+            .iter()
+            .filter_map(|(&size, winner)| winner.map(|member| (size, member)))
+            .collect();
+
+        let recommendation = match clear.first() {
+            None => format!(
+                "**{}**: the variants are within measurement noise of each other; choose on other grounds",
+                label
+            ),
+            Some(&(_, member)) if clear.iter().all(|&(_, other)| other == member) => format!(
+                "**{}**: use {}, fastest at {} of {} sizes measured",
+                label,
+                member_label(member),
+                clear.len(),
+                winners.len()
+            ),
+            Some(_) => {
+                let by_size: Vec<String> = clear
+                    .iter()
+                    .map(|&(size, member)| format!("{} at {} orders", member_label(member), size))
+                    .collect();
+                format!("**{}**: the fastest variant depends on batch size: {}", label, by_size.join(", "))
+            }
+        };
+        recommendations.push(recommendation);
+    }
+    recommendations
+}
+
+/// Renders the `**bold**` spans used in findings as HTML, escaping the rest.
+pub fn markdown_bold_to_html(finding: &str) -> String {
+    let escaped = finding.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    escaped
+        .split("**")
+        .enumerate()
+        .map(|(index, part)| {
+            if index % 2 == 1 {
+                format!("<strong>{}</strong>", part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(operation_type: &str, data_size: usize, time_ns: f64) -> BenchmarkResult {
+        BenchmarkResult::new(operation_type.to_string(), operation_type.to_string(), data_size, time_ns)
+            .with_uncertainty(time_ns, time_ns / 100.0, time_ns * 0.99, time_ns * 1.01)
+    }
+
+    #[test]
+    fn strips_task_and_runtime_qualifiers_from_the_family() {
+        assert_eq!(classify("Sync Single"), Some((String::new(), Variant::Sync, String::new())));
+        assert_eq!(classify("Async Interned"), Some(("Interned".to_string(), Variant::Async, String::new())));
+        assert_eq!(
+            classify("Async [multi_thread, 4 workers]"),
+            Some((String::new(), Variant::Async, "multi_thread, 4 workers".to_string()))
+        );
+        assert_eq!(classify("Async Spawn"), Some((String::new(), Variant::Async, "Spawn".to_string())));
+        assert_eq!(
+            classify("HFT Simulation Async Chunked 64 [current_thread]"),
+            Some(("HFT Simulation".to_string(), Variant::Async, "Chunked 64, current_thread".to_string()))
+        );
+        assert_eq!(classify("Book [unknown]"), None);
+    }
+
+    #[test]
+    fn compares_every_async_variant_against_the_sync_result() {
+        let results = vec![
+            result("Sync Single", 1000, 100_000.0),
+            result("Async", 1000, 200_000.0),
+            result("Async [multi_thread, 4 workers]", 1000, 300_000.0),
+            result("Async Spawn", 1000, 400_000.0),
+            result("Async Chunked 64", 1000, 50_000.0),
+        ];
+
+        let findings = key_findings(&results);
+
+        assert!(findings.contains(&"**Order operations**: sync is 2.0x faster than async (1 of 1 sizes)".to_string()));
+        assert!(findings.contains(
+            &"**Order operations**: sync is 3.0x faster than async (multi_thread, 4 workers) (1 of 1 sizes)".to_string()
+        ));
+        assert!(findings.contains(&"**Order operations**: sync is 4.0x faster than async (Spawn) (1 of 1 sizes)".to_string()));
+        assert!(findings.contains(
+            &"**Order operations**: async (Chunked 64) is 2.0x faster than sync (1 of 1 sizes)".to_string()
+        ));
+        assert!(!findings.iter().any(|finding| finding.contains("**Spawn**") || finding.contains("[")));
+
+        assert_eq!(
+            recommendations(&results),
+            vec!["**Order operations**: use async (Chunked 64), fastest at 1 of 1 sizes measured".to_string()]
+        );
+    }
+}
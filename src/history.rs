@@ -0,0 +1,219 @@
+use crate::environment::Environment;
+use crate::simple_plotter::BenchmarkResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Directory used when `HFT_HISTORY_DIR` is not set.
+pub const DEFAULT_HISTORY_DIR: &str = "benchmark_history";
+
+/// Environment variable that overrides the history directory.
+pub const HISTORY_DIR_ENV_VAR: &str = "HFT_HISTORY_DIR";
+
+/// JSON-lines file inside the history directory, one run per line.
+pub const HISTORY_FILE_NAME: &str = "runs.jsonl";
+
+/// Every result of one benchmark run, keyed by when, where and at which
+/// commit it was produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub timestamp: DateTime<Utc>,
+    pub git_commit: Option<String>,
+    pub host: String,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub environment: Option<Environment>,
+    pub results: Vec<BenchmarkResult>,
+}
+
+impl RunRecord {
+    /// Stamps `results` with the current time, and with the git commit and
+    /// host of the `environment` they were measured in. The current commit
+    /// and host stand in only when no environment was recorded, e.g. for
+    /// results exported before environments existed.
+    pub fn capture(results: Vec<BenchmarkResult>, seed: Option<u64>, environment: Option<Environment>) -> Self {
+        let (git_commit, host) = match &environment {
+            Some(environment) => (environment.git_commit.clone(), environment.host.clone()),
+            None => (current_git_commit(), current_host()),
+        };
+        Self {
+            timestamp: Utc::now(),
+            git_commit,
+            host,
+            seed,
+            environment,
+            results,
+        }
+    }
+
+    /// Short label for table headers: abbreviated commit, or the date.
+    pub fn label(&self) -> String {
+        match &self.git_commit {
+            Some(commit) => commit.chars().take(8).collect(),
+            None => self.timestamp.format("%m-%d %H:%M").to_string(),
+        }
+    }
+
+    fn same_results(&self, other: &RunRecord) -> bool {
+        self.results.len() == other.results.len()
+            && self
+                .results
+                .iter()
+                .zip(&other.results)
+                .all(|(a, b)| a.name == b.name && a.time_ns == b.time_ns)
+    }
+}
+
+/// Append-only store of [`RunRecord`]s in a directory.
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    dir: PathBuf,
+}
+
+impl HistoryStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Store in `HFT_HISTORY_DIR`, falling back to [`DEFAULT_HISTORY_DIR`].
+    pub fn from_env() -> Self {
+        Self::new(std::env::var(HISTORY_DIR_ENV_VAR).unwrap_or_else(|_| DEFAULT_HISTORY_DIR.to_string()))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(HISTORY_FILE_NAME)
+    }
+
+    /// Appends `record` unless the latest run from the same host already has
+    /// identical results, so re-reporting one Criterion run is not counted
+    /// twice. Returns whether the record was written.
+    pub fn append(&self, record: &RunRecord) -> Result<bool, Box<dyn std::error::Error>> {
+        let duplicate = self
+            .load()?
+            .iter()
+            .rev()
+            .find(|existing| existing.host == record.host)
+            .is_some_and(|latest| latest.same_results(record));
+        if duplicate {
+            return Ok(false);
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(self.path())?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(true)
+    }
+
+    /// All runs in the order they were recorded. Lines that fail to parse
+    /// are skipped so one bad write does not hide the rest of the history.
+    pub fn load(&self) -> Result<Vec<RunRecord>, Box<dyn std::error::Error>> {
+        let path = self.path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let records = fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        Ok(records)
+    }
+
+    /// The most recent `count` runs, oldest first.
+    pub fn recent(&self, count: usize) -> Result<Vec<RunRecord>, Box<dyn std::error::Error>> {
+        let mut records = self.load()?;
+        let skip = records.len().saturating_sub(count);
+        Ok(records.split_off(skip))
+    }
+}
+
+pub fn current_git_commit() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    if commit.is_empty() {
+        None
+    } else {
+        Some(commit)
+    }
+}
+
+pub fn current_host() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(time_ns: f64) -> BenchmarkResult {
+        BenchmarkResult::new("group/async/100".to_string(), "Async".to_string(), 100, time_ns)
+    }
+
+    fn store(name: &str) -> HistoryStore {
+        let dir = std::env::temp_dir().join(format!("hft-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        HistoryStore::new(dir)
+    }
+
+    #[test]
+    fn takes_commit_and_host_from_the_environment() {
+        let environment = Environment {
+            host: "bench-01".to_string(),
+            git_commit: Some("abc123".to_string()),
+            ..Environment::default()
+        };
+        let record = RunRecord::capture(vec![result(1.0)], Some(42), Some(environment.clone()));
+
+        assert_eq!(record.host, "bench-01");
+        assert_eq!(record.git_commit.as_deref(), Some("abc123"));
+        assert_eq!(record.environment, Some(environment));
+    }
+
+    #[test]
+    fn appends_and_reads_runs_in_order() {
+        let store = store("append");
+        let first = RunRecord::capture(vec![result(1_000.0)], Some(1), None);
+        let second = RunRecord::capture(vec![result(2_000.0)], Some(2), None);
+
+        assert!(store.append(&first).unwrap());
+        assert!(store.append(&second).unwrap());
+        let runs = store.load().unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].seed, Some(1));
+        assert_eq!(runs[1].results[0].time_ns, 2_000.0);
+        assert_eq!(store.recent(1).unwrap()[0].seed, Some(2));
+
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn skips_repeated_results_and_bad_lines() {
+        let store = store("duplicate");
+        let record = RunRecord::capture(vec![result(1_000.0)], None, None);
+
+        assert!(store.append(&record).unwrap());
+        assert!(!store.append(&record).unwrap());
+        let mut file = OpenOptions::new().append(true).open(store.path()).unwrap();
+        writeln!(file, "not json").unwrap();
+        assert_eq!(store.load().unwrap().len(), 1);
+
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Environment variable selecting which key strategies the benchmarks run.
+pub const KEY_STRATEGY_ENV_VAR: &str = "HFT_KEY_STRATEGY";
+
+/// Maps strings to dense `u32` ids and back.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, u32>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id for `name`, allocating only the first time it is seen.
+    pub fn intern(&mut self, name: &str) -> u32 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = u32::try_from(self.names.len()).expect("interner holds at most u32::MAX strings");
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn resolve(&self, id: u32) -> Option<&str> {
+        self.names.get(id as usize).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SymbolId(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ClientId(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct OrderId(pub u64);
+
+impl fmt::Display for OrderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ORD_{}", self.0)
+    }
+}
+
+/// Separate interners for symbols and clients so each id space stays dense.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: Interner,
+    clients: Interner,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn symbol(&mut self, name: &str) -> SymbolId {
+        SymbolId(self.symbols.intern(name))
+    }
+
+    pub fn client(&mut self, name: &str) -> ClientId {
+        ClientId(self.clients.intern(name))
+    }
+
+    pub fn symbol_name(&self, id: SymbolId) -> Option<&str> {
+        self.symbols.resolve(id.0)
+    }
+
+    pub fn client_name(&self, id: ClientId) -> Option<&str> {
+        self.clients.resolve(id.0)
+    }
+}
+
+/// How benchmarks key orders and identify symbols and clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyStrategy {
+    /// `format!("ORD_{}")` map keys and owned `String` symbols/clients.
+    Strings,
+    /// Numeric [`OrderId`] keys and interned symbol/client ids.
+    Interned,
+}
+
+impl KeyStrategy {
+    pub const ALL: [KeyStrategy; 2] = [KeyStrategy::Strings, KeyStrategy::Interned];
+
+    /// Suffix appended to benchmark function names; string keys, the only
+    /// keys before interning, add none.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            KeyStrategy::Strings => "",
+            KeyStrategy::Interned => "_interned",
+        }
+    }
+
+    /// Strategies to run for a scenario that selects `scenario`:
+    /// `HFT_KEY_STRATEGY` overrides it for a whole run when set.
+    pub fn selected(scenario: KeyStrategySelection) -> Result<Vec<KeyStrategy>, String> {
+        Ok(KeyStrategySelection::from_env()?.unwrap_or(scenario).strategies())
+    }
+}
+
+/// Key strategies to benchmark, as `strings`, `interned` or `all` in
+/// `HFT_KEY_STRATEGY` or a scenario's `key_strategy`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum KeyStrategySelection {
+    Strings,
+    Interned,
+    #[default]
+    All,
+}
+
+impl KeyStrategySelection {
+    pub fn parse(value: &str) -> Option<KeyStrategySelection> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "strings" | "string" => Some(KeyStrategySelection::Strings),
+            "interned" => Some(KeyStrategySelection::Interned),
+            "all" | "" => Some(KeyStrategySelection::All),
+            _ => None,
+        }
+    }
+
+    /// The selection in `HFT_KEY_STRATEGY`, if set. An unknown value is an
+    /// error, as it is in a scenario file.
+    pub fn from_env() -> Result<Option<KeyStrategySelection>, String> {
+        Self::from_env_value(std::env::var(KEY_STRATEGY_ENV_VAR).ok())
+    }
+
+    fn from_env_value(value: Option<String>) -> Result<Option<KeyStrategySelection>, String> {
+        value
+            .map(|value| Self::try_from(value).map_err(|err| format!("{}: {}", KEY_STRATEGY_ENV_VAR, err)))
+            .transpose()
+    }
+
+    pub fn strategies(&self) -> Vec<KeyStrategy> {
+        match self {
+            KeyStrategySelection::Strings => vec![KeyStrategy::Strings],
+            KeyStrategySelection::Interned => vec![KeyStrategy::Interned],
+            KeyStrategySelection::All => KeyStrategy::ALL.to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for KeyStrategySelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyStrategySelection::Strings => write!(f, "strings"),
+            KeyStrategySelection::Interned => write!(f, "interned"),
+            KeyStrategySelection::All => write!(f, "all"),
+        }
+    }
+}
+
+impl TryFrom<String> for KeyStrategySelection {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        KeyStrategySelection::parse(&value)
+            .ok_or_else(|| format!("unknown key strategy '{}' (expected strings, interned or all)", value))
+    }
+}
+
+impl From<KeyStrategySelection> for String {
+    fn from(selection: KeyStrategySelection) -> Self {
+        selection.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_each_name_once() {
+        let mut interner = Interner::new();
+        let aapl = interner.intern("AAPL");
+        let msft = interner.intern("MSFT");
+
+        assert_eq!(interner.intern("AAPL"), aapl);
+        assert_ne!(aapl, msft);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("MSFT"), Some(msft));
+        assert_eq!(interner.get("GOOG"), None);
+        assert_eq!(interner.resolve(aapl), Some("AAPL"));
+        assert_eq!(interner.resolve(msft), Some("MSFT"));
+        assert_eq!(interner.resolve(2), None);
+    }
+
+    #[test]
+    fn symbol_table_keeps_symbols_and_clients_apart() {
+        let mut table = SymbolTable::new();
+        let symbol = table.symbol("STOCK_1");
+        let client = table.client("CLIENT_1");
+
+        assert_eq!(symbol, SymbolId(0));
+        assert_eq!(client, ClientId(0));
+        assert_eq!(table.symbol("STOCK_1"), symbol);
+        assert_eq!(table.symbol_name(symbol), Some("STOCK_1"));
+        assert_eq!(table.client_name(client), Some("CLIENT_1"));
+        assert_eq!(table.client_name(ClientId(1)), None);
+    }
+
+    #[test]
+    fn order_ids_format_like_string_keys() {
+        assert_eq!(OrderId(42).to_string(), "ORD_42");
+        assert_eq!(OrderId(0).to_string(), format!("ORD_{}", 0));
+    }
+
+    #[test]
+    fn parses_key_strategy_selections() {
+        assert_eq!(KeyStrategySelection::parse("strings"), Some(KeyStrategySelection::Strings));
+        assert_eq!(KeyStrategySelection::parse(" String "), Some(KeyStrategySelection::Strings));
+        assert_eq!(KeyStrategySelection::parse("INTERNED"), Some(KeyStrategySelection::Interned));
+        assert_eq!(KeyStrategySelection::parse(""), Some(KeyStrategySelection::All));
+        assert_eq!(KeyStrategySelection::parse("numeric"), None);
+        assert_eq!(KeyStrategySelection::All.strategies(), KeyStrategy::ALL.to_vec());
+        assert_eq!(KeyStrategySelection::Interned.strategies(), vec![KeyStrategy::Interned]);
+    }
+
+    #[test]
+    fn key_strategy_selection_round_trips_through_json() {
+        let selection: KeyStrategySelection = serde_json::from_str(r#""interned""#).unwrap();
+        assert_eq!(selection, KeyStrategySelection::Interned);
+        assert_eq!(serde_json::to_string(&KeyStrategySelection::All).unwrap(), r#""all""#);
+        assert!(serde_json::from_str::<KeyStrategySelection>(r#""numeric""#).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_env_selections() {
+        assert_eq!(KeyStrategySelection::from_env_value(None), Ok(None));
+        assert_eq!(
+            KeyStrategySelection::from_env_value(Some("interned".to_string())),
+            Ok(Some(KeyStrategySelection::Interned))
+        );
+        let err = KeyStrategySelection::from_env_value(Some("intern".to_string())).unwrap_err();
+        assert!(err.starts_with("HFT_KEY_STRATEGY: unknown key strategy 'intern'"), "{}", err);
+    }
+}
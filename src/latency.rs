@@ -0,0 +1,227 @@
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// File written next to the Criterion output holding the latency summaries.
+pub const LATENCY_FILE_NAME: &str = "latency.json";
+
+/// Highest latency the histograms track; slower samples are clamped to it.
+const MAX_TRACKABLE_NS: u64 = 60_000_000_000;
+
+/// Significant figures kept by each histogram.
+const SIGNIFICANT_FIGURES: u8 = 3;
+
+/// Individual operation timed by a [`LatencyRecorder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Operation {
+    Insert,
+    Get,
+    Update,
+    /// A whole order request, measured from its intended send time.
+    Order,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 4] = [Operation::Insert, Operation::Get, Operation::Update, Operation::Order];
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Operation::Insert => "insert",
+            Operation::Get => "get",
+            Operation::Update => "update",
+            Operation::Order => "order",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Tail latency of one operation within one scenario, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub scenario: String,
+    pub operation: Operation,
+    pub count: u64,
+    pub mean_ns: f64,
+    pub p50_ns: u64,
+    pub p90_ns: u64,
+    pub p99_ns: u64,
+    pub p999_ns: u64,
+    pub max_ns: u64,
+}
+
+/// One HDR histogram per [`Operation`].
+///
+/// Each sample is taken with its own pair of `Instant::now()` calls, so the
+/// recorded values include a few tens of nanoseconds of clock overhead.
+#[derive(Debug, Clone)]
+pub struct LatencyRecorder {
+    histograms: BTreeMap<Operation, Histogram<u64>>,
+}
+
+impl LatencyRecorder {
+    pub fn new() -> Self {
+        let histograms = Operation::ALL
+            .iter()
+            .map(|&operation| {
+                let histogram = Histogram::new_with_bounds(1, MAX_TRACKABLE_NS, SIGNIFICANT_FIGURES)
+                    .expect("histogram bounds are valid");
+                (operation, histogram)
+            })
+            .collect();
+        Self { histograms }
+    }
+
+    pub fn record(&mut self, operation: Operation, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.record_ns(operation, nanos);
+    }
+
+    pub fn record_ns(&mut self, operation: Operation, nanos: u64) {
+        let histogram = self.histograms.get_mut(&operation).expect("histogram per operation");
+        histogram.saturating_record(nanos.max(1));
+    }
+
+    /// Runs `f`, records how long it took and returns its result.
+    pub fn time<T>(&mut self, operation: Operation, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let value = f();
+        self.record(operation, start.elapsed());
+        value
+    }
+
+    /// Adds every sample recorded by `other`.
+    pub fn merge(&mut self, other: &LatencyRecorder) {
+        for (operation, histogram) in &mut self.histograms {
+            histogram.add(&other.histograms[operation]).expect("histograms share bounds");
+        }
+    }
+
+    pub fn count(&self, operation: Operation) -> u64 {
+        self.histograms[&operation].len()
+    }
+
+    /// Percentile summaries for every operation that has samples.
+    pub fn summaries(&self, scenario: &str) -> Vec<LatencySummary> {
+        self.histograms
+            .iter()
+            .filter(|(_, histogram)| !histogram.is_empty())
+            .map(|(&operation, histogram)| LatencySummary {
+                scenario: scenario.to_string(),
+                operation,
+                count: histogram.len(),
+                mean_ns: histogram.mean(),
+                p50_ns: histogram.value_at_quantile(0.50),
+                p90_ns: histogram.value_at_quantile(0.90),
+                p99_ns: histogram.value_at_quantile(0.99),
+                p999_ns: histogram.value_at_quantile(0.999),
+                max_ns: histogram.max(),
+            })
+            .collect()
+    }
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Merges `summaries` into the latency file in `dir`, replacing any earlier
+/// entries for the same scenarios so separate benchmark groups can share it.
+pub fn record_summaries(dir: &Path, summaries: &[LatencySummary]) -> std::io::Result<()> {
+    let mut merged = load_summaries(dir).unwrap_or_default();
+    merged.retain(|existing| !summaries.iter().any(|new| new.scenario == existing.scenario));
+    merged.extend_from_slice(summaries);
+    merged.sort_by(|a, b| a.scenario.cmp(&b.scenario).then(a.operation.cmp(&b.operation)));
+
+    fs::create_dir_all(dir)?;
+    let json = serde_json::to_string_pretty(&merged)?;
+    fs::write(dir.join(LATENCY_FILE_NAME), json)
+}
+
+pub fn load_summaries(dir: &Path) -> Option<Vec<LatencySummary>> {
+    fs::read_to_string(dir.join(LATENCY_FILE_NAME))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_recorded_percentiles() {
+        let mut recorder = LatencyRecorder::new();
+        for nanos in 1..=1_000 {
+            recorder.record_ns(Operation::Insert, nanos);
+        }
+        recorder.record(Operation::Get, Duration::from_micros(5));
+
+        let summaries = recorder.summaries("orders/100");
+        assert_eq!(summaries.len(), 2, "operations without samples are left out");
+        let insert = &summaries[0];
+        assert_eq!((insert.scenario.as_str(), insert.operation, insert.count), ("orders/100", Operation::Insert, 1_000));
+        assert!((insert.mean_ns - 500.5).abs() < 1.0, "{}", insert.mean_ns);
+        assert_eq!(insert.p50_ns, 500);
+        assert_eq!(insert.p90_ns, 900);
+        assert_eq!(insert.p99_ns, 990);
+        assert_eq!(insert.max_ns, 1_000);
+        // Exact below 2048, within three significant figures above
+        assert!(summaries[1].p50_ns.abs_diff(5_000) <= 5, "{}", summaries[1].p50_ns);
+    }
+
+    #[test]
+    fn clamps_values_outside_the_histogram_range() {
+        let mut recorder = LatencyRecorder::new();
+        recorder.record(Operation::Update, Duration::ZERO);
+        recorder.record(Operation::Update, Duration::from_secs(3_600));
+        recorder.record(Operation::Update, Duration::MAX);
+
+        let summary = &recorder.summaries("clamped")[0];
+        assert_eq!(summary.count, 3);
+        assert_eq!(recorder.histograms[&Operation::Update].min(), 1);
+        // Clamped to the trackable maximum, within the histogram's precision
+        let tolerance = MAX_TRACKABLE_NS / 10u64.pow(SIGNIFICANT_FIGURES as u32 - 1);
+        assert!(summary.max_ns.abs_diff(MAX_TRACKABLE_NS) <= tolerance, "{}", summary.max_ns);
+    }
+
+    #[test]
+    fn merges_recorders() {
+        let mut first = LatencyRecorder::new();
+        let mut second = LatencyRecorder::new();
+        first.record_ns(Operation::Order, 100);
+        second.record_ns(Operation::Order, 300);
+        second.record_ns(Operation::Insert, 50);
+
+        first.merge(&second);
+        assert_eq!(first.count(Operation::Order), 2);
+        assert_eq!(first.count(Operation::Insert), 1);
+        assert_eq!(first.count(Operation::Get), 0);
+    }
+
+    #[test]
+    fn recorded_summaries_replace_earlier_scenarios() {
+        let dir = std::env::temp_dir().join(format!("hft-latency-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let summary = |scenario: &str, p50_ns| {
+            let mut recorder = LatencyRecorder::new();
+            recorder.record_ns(Operation::Get, p50_ns);
+            recorder.summaries(scenario).remove(0)
+        };
+
+        record_summaries(&dir, &[summary("b", 10), summary("a", 10)]).unwrap();
+        record_summaries(&dir, &[summary("b", 20)]).unwrap();
+
+        let loaded = load_summaries(&dir).unwrap();
+        let scenarios: Vec<(&str, u64)> = loaded.iter().map(|s| (s.scenario.as_str(), s.p50_ns)).collect();
+        assert_eq!(scenarios, vec![("a", 10), ("b", 20)]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
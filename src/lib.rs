@@ -1,3 +1,36 @@
+pub mod compare;
+pub mod environment;
+pub mod findings;
+pub mod history;
+pub mod interner;
+pub mod latency;
+pub mod open_loop;
+pub mod order_book;
+pub mod orders;
+#[cfg(feature = "charts")]
+pub mod plotter;
+pub mod price;
+pub mod scenario_file;
+pub mod scenarios;
 pub mod simple_plotter;
+pub mod stop_orders;
+pub mod svg_chart;
+pub mod workload;
 
-pub use simple_plotter::{SimplePlotter, BenchmarkResult};
\ No newline at end of file
+pub use compare::{Comparison, ComparisonReport, Verdict};
+pub use environment::Environment;
+pub use history::{HistoryStore, RunRecord};
+pub use interner::{ClientId, Interner, KeyStrategy, KeyStrategySelection, OrderId, SymbolId, SymbolTable};
+pub use latency::{LatencyRecorder, LatencySummary, Operation};
+pub use open_loop::{RunStats, Schedule};
+pub use order_book::{BookError, OrderBook, OrderBooks, TickOrderBook, TickOrderBooks, Trade};
+pub use orders::{CompactOrder, Order, OrderError, OrderSide, OrderStatus, OrderType};
+pub use price::{Price, PriceError, SymbolSpec, SymbolSpecs};
+pub use scenario_file::{ScenarioFile, ScenarioSpec, WorkloadSpec};
+pub use scenarios::{Scenario, ScenarioRunner, TaskStrategy};
+pub use stop_orders::{StopTriggers, SubmitError, TriggerNotifier, TriggerOutcome};
+pub use svg_chart::SvgChart;
+pub use workload::{OrderWorkload, WorkloadGenerator};
+pub use simple_plotter::{SimplePlotter, BenchmarkResult, ReportFormat, ResultsDocument};
+#[cfg(feature = "charts")]
+pub use plotter::BenchmarkPlotter;
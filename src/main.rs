@@ -1,25 +1,250 @@
-mod simple_plotter;
+use benchmark_async_vs_sync::environment::Environment;
+use benchmark_async_vs_sync::interner::KeyStrategySelection;
+use benchmark_async_vs_sync::scenario_file::{CriterionSettings, RuntimeSettings, ScenarioFile, ScenarioSpec, WorkloadSpec};
+use benchmark_async_vs_sync::scenarios::{Scenario, ScenarioRunner, TaskStrategy};
+use benchmark_async_vs_sync::simple_plotter::ReportFormat;
+use benchmark_async_vs_sync::workload::{self, OrderWorkload};
+use benchmark_async_vs_sync::{BenchmarkResult, SimplePlotter};
+use clap::{Arg, ArgAction, Command};
+use std::path::PathBuf;
 
-use simple_plotter::SimplePlotter;
+fn cli() -> Command<'static> {
+    Command::new("benchmark-async-vs-sync")
+        .about("Times the sync, DashMap and Tokio order workloads outside Criterion and reports the results")
+        .arg(
+            Arg::new("scenario")
+                .short('s')
+                .long("scenario")
+                .value_name("LIST")
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .value_parser(|value: &str| {
+                    Scenario::parse(value).ok_or_else(|| format!("unknown scenario '{}' (expected sync, dashmap or tokio)", value))
+                })
+                .default_value("sync,dashmap,tokio")
+                .help("Comma-separated scenarios to run: sync, dashmap, tokio"),
+        )
+        .arg(
+            Arg::new("sizes")
+                .long("sizes")
+                .value_name("LIST")
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .value_parser(clap::value_parser!(usize))
+                .default_value("100,1000,10000")
+                .help("Comma-separated numbers of orders per run"),
+        )
+        .arg(
+            Arg::new("runtime")
+                .long("runtime")
+                .value_name("LIST")
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .value_parser(|value: &str| {
+                    RuntimeSettings::parse(value).ok_or_else(|| format!("invalid runtime '{}' (expected e.g. default, current_thread, mt4, mt4-nolifo, mt-ev31-gq61)", value))
+                })
+                .default_value("default")
+                .help("Comma-separated Tokio runtimes for the tokio scenario: default, current_thread or mt<N>, plus -nolifo, -ev<N> and -gq<N> modifiers"),
+        )
+        .arg(
+            Arg::new("tasks")
+                .long("tasks")
+                .value_name("LIST")
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .value_parser(|value: &str| TaskStrategy::try_from(value.to_string()))
+                .default_value("join_all")
+                .help("Comma-separated ways the tokio scenario drives its futures: join_all, spawn, join_set, futures_unordered, chunked:<size>"),
+        )
+        .arg(
+            Arg::new("file")
+                .short('f')
+                .long("file")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(PathBuf))
+                .conflicts_with_all(&["scenario", "sizes", "runtime", "tasks"])
+                .help("Run the order_operations scenarios of a scenario file such as scenarios.json instead"),
+        )
+        .arg(
+            Arg::new("runs")
+                .long("runs")
+                .value_name("N")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(usize))
+                .default_value("20")
+                .help("Timed runs per scenario and size (at least 2)"),
+        )
+        .arg(
+            Arg::new("warmup")
+                .long("warmup")
+                .value_name("N")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(usize))
+                .default_value("3")
+                .help("Untimed warm-up runs per scenario and size"),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .value_name("SEED")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(u64))
+                .help("Workload seed [default: HFT_BENCH_SEED or the built-in seed]"),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("DIR")
+                .action(ArgAction::Set)
+                .default_value("hft_benchmark_report")
+                .help("Directory the report files are written to"),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("LIST")
+                .action(ArgAction::Append)
+                .use_value_delimiter(true)
+                .value_parser(|value: &str| {
+                    ReportFormat::parse(value).ok_or_else(|| format!("unknown format '{}' (expected csv, json, md, html or txt)", value))
+                })
+                .default_value("csv,json,md,html,txt")
+                .help("Comma-separated report files to write: csv, json, md, html, txt"),
+        )
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 HFT Order Processing Benchmark Analyzer");
-    
+    let args = cli().get_matches();
+    let runner = ScenarioRunner::new(
+        *args.get_one::<usize>("warmup").expect("argument has a default"),
+        *args.get_one::<usize>("runs").expect("argument has a default"),
+    )?;
+    let seed = match args.get_one::<u64>("seed") {
+        Some(&seed) => seed,
+        None => workload::seed_from_env()?,
+    };
+    let output_dir = args.get_one::<String>("output").expect("argument has a default");
+    let formats: Vec<ReportFormat> = args.get_many::<ReportFormat>("format").expect("argument has a default").copied().collect();
+
+    println!("🚀 HFT Order Processing Scenario Runner");
+    println!("🎲 Workload seed: {}", seed);
+    if cfg!(debug_assertions) {
+        println!("⚠️  Debug build: timings are not representative, run with --release");
+    }
+
     let mut plotter = SimplePlotter::new();
-    
-    // Add sample benchmark results (replace with actual results from your benchmarks)
-    plotter.add_sample_results();
-    
-    // Print visual analysis
-    plotter.print_ascii_chart();
-    plotter.print_comparison_table();
-    plotter.print_scalability_analysis();
-    
-    // Generate complete report
-    plotter.generate_complete_report("hft_benchmark_report")?;
-    
-    println!("\n✅ Benchmark analysis complete!");
-    println!("💡 Run 'cargo bench' first to get real benchmark data");
-    
+    plotter.set_seed(seed);
+    plotter.set_environment(Environment::capture());
+
+    let file = match args.get_one::<PathBuf>("file") {
+        Some(path) => ScenarioFile::load(path)?,
+        None => ScenarioFile {
+            scenarios: command_line_specs(
+                args.get_many::<Scenario>("scenario").expect("argument has a default").copied().collect(),
+                args.get_many::<usize>("sizes").expect("argument has a default").copied().collect(),
+                args.get_many::<RuntimeSettings>("runtime").expect("argument has a default").copied().collect(),
+                args.get_many::<TaskStrategy>("tasks").expect("argument has a default").copied().collect(),
+            )?,
+        },
+    };
+    for spec in &file.scenarios {
+        run_spec(spec, &runner, seed, &mut plotter)?;
+    }
+
+    plotter.generate_report(output_dir, &formats)?;
+
+    println!("\n✅ Scenario run complete!");
+
     Ok(())
 }
+
+// The command line scenarios grouped the way scenarios.json groups them,
+// so results are named like the Criterion benchmarks, and checked the way
+// a scenario file is checked
+fn command_line_specs(
+    scenarios: Vec<Scenario>,
+    sizes: Vec<usize>,
+    runtimes: Vec<RuntimeSettings>,
+    tasks: Vec<TaskStrategy>,
+) -> Result<Vec<ScenarioSpec>, String> {
+    let mut specs: Vec<ScenarioSpec> = Vec::new();
+    for scenario in scenarios {
+        let group = scenario.benchmark_id().0;
+        match specs.iter_mut().find(|spec| spec.name == group) {
+            Some(spec) if !spec.execution.contains(&scenario) => spec.execution.push(scenario),
+            Some(_) => {}
+            None => specs.push(ScenarioSpec {
+                name: group.to_string(),
+                workload: WorkloadSpec::OrderOperations {
+                    sizes: sizes.clone(),
+                    symbols: 1000,
+                    clients: 100,
+                },
+                execution: vec![scenario],
+                runtimes: runtimes.clone(),
+                tasks: tasks.clone(),
+                key_strategy: KeyStrategySelection::default(),
+                criterion: CriterionSettings::default(),
+            }),
+        }
+    }
+    for spec in &specs {
+        spec.validate().map_err(|err| format!("scenario '{}': {}", spec.name, err))?;
+    }
+    Ok(specs)
+}
+
+// Only order_operations workloads have runner implementations; the other
+// workload types are left to the Criterion bench. Tokio runs once per task
+// strategy and runtime, the sync scenarios once on a current_thread runtime
+// they ignore.
+fn run_spec(spec: &ScenarioSpec, runner: &ScenarioRunner, seed: u64, plotter: &mut SimplePlotter) -> Result<(), Box<dyn std::error::Error>> {
+    let WorkloadSpec::OrderOperations { sizes, symbols, clients } = &spec.workload else {
+        println!("⏭️  Skipping {}: {} workloads only run under cargo bench", spec.name, spec.workload.kind());
+        return Ok(());
+    };
+
+    println!("📋 {}", spec.name);
+    let sync_runtime = (RuntimeSettings::current_thread(), RuntimeSettings::current_thread().build()?);
+    let mut runtimes = Vec::new();
+    for settings in &spec.runtimes {
+        match settings.build() {
+            Ok(runtime) => runtimes.push((*settings, runtime)),
+            Err(err) => println!("⏭️  Skipping runtime {}: {}", settings.describe(), err),
+        }
+    }
+
+    for &size in sizes {
+        let workload = OrderWorkload::generate(seed, size, *symbols, *clients);
+        for (scenario, _) in spec.functions() {
+            if scenario != Scenario::Tokio {
+                let (settings, runtime) = &sync_runtime;
+                let result = runner.run_on(&spec.name, scenario, TaskStrategy::JoinAll, settings, &workload, runtime);
+                print_result(&scenario.to_string(), size, &result, runner);
+                plotter.add_result(result);
+                continue;
+            }
+            for &tasks in &spec.tasks {
+                for (settings, runtime) in &runtimes {
+                    let result = runner.run_on(&spec.name, scenario, tasks, settings, &workload, runtime);
+                    print_result(&format!("{}{}{}", scenario, tasks.suffix(), settings.label()), size, &result, runner);
+                    plotter.add_result(result);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_result(name: &str, size: usize, result: &BenchmarkResult, runner: &ScenarioRunner) {
+    println!("⏱️  {:<32} {:>8} orders: {:>12.1}µs ±{:.1}% over {} runs",
+        name,
+        size,
+        result.time_us,
+        result.relative_error_pct(),
+        runner.timed_runs()
+    );
+}
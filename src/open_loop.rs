@@ -0,0 +1,223 @@
+use crate::latency::{LatencyRecorder, Operation};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Target send rate used when `HFT_OPEN_LOOP_RATE` is not set.
+pub const DEFAULT_TARGET_RATE: u64 = 1_000_000;
+
+/// Environment variable that overrides the open-loop target rate (orders/sec).
+pub const TARGET_RATE_ENV_VAR: &str = "HFT_OPEN_LOOP_RATE";
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Fixed-rate send schedule: order `i` is due `i / rate` seconds after the
+/// start of the run, whether or not earlier orders have completed.
+///
+/// Each send time is computed from the start rather than by adding up a
+/// rounded interval, so rates that do not divide a second evenly stay on
+/// target and rates above one order per nanosecond still spread out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    rate_per_sec: u64,
+}
+
+impl Schedule {
+    pub fn new(rate_per_sec: u64) -> Self {
+        Self {
+            rate_per_sec: rate_per_sec.max(1),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        target_rate_from_env().map(Self::new)
+    }
+
+    pub fn rate_per_sec(&self) -> u64 {
+        self.rate_per_sec
+    }
+
+    /// Time from the start of a run until order `i` is due.
+    pub fn offset(&self, i: u64) -> Duration {
+        let nanos = i as u128 * NANOS_PER_SEC / self.rate_per_sec as u128;
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+
+    /// Intended send time of order `i` in a run that started at `start`.
+    pub fn intended(&self, start: Instant, i: u64) -> Instant {
+        start + self.offset(i)
+    }
+}
+
+/// Outcome of one open-loop run.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunStats {
+    pub orders: u64,
+    /// Orders sent after the next order was already due.
+    pub late: u64,
+    /// Sum of intended-send-to-completion latencies.
+    pub total_latency: Duration,
+    pub elapsed: Duration,
+}
+
+impl RunStats {
+    pub fn achieved_rate(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            0.0
+        } else {
+            self.orders as f64 / self.elapsed.as_secs_f64()
+        }
+    }
+
+    pub fn merge(&mut self, other: &RunStats) {
+        self.orders += other.orders;
+        self.late += other.late;
+        self.total_latency += other.total_latency;
+        self.elapsed += other.elapsed;
+    }
+}
+
+/// The rate in `HFT_OPEN_LOOP_RATE`, or [`DEFAULT_TARGET_RATE`] when it is
+/// not set. Fails on anything but a positive whole number.
+pub fn target_rate_from_env() -> Result<u64, String> {
+    let Ok(value) = std::env::var(TARGET_RATE_ENV_VAR) else {
+        return Ok(DEFAULT_TARGET_RATE);
+    };
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|&rate| rate > 0)
+        .ok_or_else(|| format!("{} must be a positive number of orders per second, got '{}'", TARGET_RATE_ENV_VAR, value))
+}
+
+fn spin_until(deadline: Instant) {
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
+
+/// Issues `count` requests inline on the calling thread following `schedule`.
+///
+/// The driver waits for each intended send time but never skips one: if a
+/// request overruns, the following requests start late and that queueing
+/// delay is part of their latency, which a closed loop would hide.
+pub fn run_sync(
+    schedule: Schedule,
+    count: u64,
+    recorder: &mut LatencyRecorder,
+    mut request: impl FnMut(u64),
+) -> RunStats {
+    let mut stats = RunStats::default();
+    let start = Instant::now();
+
+    for i in 0..count {
+        let intended = schedule.intended(start, i);
+        spin_until(intended);
+        if Instant::now() > schedule.intended(start, i + 1) {
+            stats.late += 1;
+        }
+
+        request(i);
+
+        let latency = intended.elapsed();
+        recorder.record(Operation::Order, latency);
+        stats.total_latency += latency;
+    }
+
+    stats.orders = count;
+    stats.elapsed = start.elapsed();
+    stats
+}
+
+/// Spawns one Tokio task per request at its intended send time and measures
+/// each from that time until the task completes.
+///
+/// Must be called from within a Tokio runtime. The producer yields while it
+/// waits so tasks can progress on a current-thread runtime too.
+pub async fn run_tokio<F, Fut>(
+    schedule: Schedule,
+    count: u64,
+    recorder: &mut LatencyRecorder,
+    mut request: F,
+) -> RunStats
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut stats = RunStats::default();
+    let mut handles = Vec::with_capacity(count as usize);
+    let start = Instant::now();
+
+    for i in 0..count {
+        let intended = schedule.intended(start, i);
+        while Instant::now() < intended {
+            tokio::task::yield_now().await;
+        }
+        if Instant::now() > schedule.intended(start, i + 1) {
+            stats.late += 1;
+        }
+
+        let future = request(i);
+        handles.push(tokio::spawn(async move {
+            future.await;
+            intended.elapsed()
+        }));
+    }
+
+    for handle in handles {
+        let latency = handle.await.expect("open-loop request task panicked");
+        recorder.record(Operation::Order, latency);
+        stats.total_latency += latency;
+    }
+
+    stats.orders = count;
+    stats.elapsed = start.elapsed();
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_do_not_drift() {
+        // 3 orders/sec does not divide a second evenly
+        let schedule = Schedule::new(3);
+        assert_eq!(schedule.offset(0), Duration::ZERO);
+        assert_eq!(schedule.offset(1), Duration::from_nanos(333_333_333));
+        assert_eq!(schedule.offset(3), Duration::from_secs(1));
+        assert_eq!(schedule.offset(3_000_000), Duration::from_secs(1_000_000));
+    }
+
+    #[test]
+    fn rates_above_one_per_nanosecond_still_spread_out() {
+        let schedule = Schedule::new(4_000_000_000);
+        assert_eq!(schedule.offset(3), Duration::ZERO);
+        assert_eq!(schedule.offset(4), Duration::from_nanos(1));
+        assert_eq!(schedule.offset(4_000_000_000), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn zero_rate_is_raised_to_one() {
+        assert_eq!(Schedule::new(0).offset(2), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn late_starts_count_toward_latency() {
+        // Every request takes three intervals, so each order starts further
+        // behind schedule than the last and waits for that in its latency
+        let interval = Duration::from_millis(2);
+        let schedule = Schedule::new(500);
+        let mut recorder = LatencyRecorder::new();
+        let stats = run_sync(schedule, 5, &mut recorder, |_| std::thread::sleep(interval * 3));
+
+        assert_eq!(stats.orders, 5);
+        assert_eq!(recorder.count(Operation::Order), 5);
+        assert!(stats.late >= 4, "{:?}", stats);
+        // Order i is due at i intervals and done no earlier than 3 * (i + 1)
+        let queued: u32 = (0..5).map(|i| 3 * (i + 1) - i).sum();
+        assert!(stats.total_latency >= interval * queued, "{:?}", stats);
+        let slowest = recorder.summaries("test")[0].max_ns;
+        assert!(slowest >= (interval * 11).as_nanos() as u64);
+    }
+}
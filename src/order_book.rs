@@ -0,0 +1,643 @@
+use crate::orders::{Order, OrderError, OrderSide, OrderStatus, OrderType};
+use crate::price::{Price, PriceError, SymbolSpec, SymbolSpecs, PRICE_SCALE};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Trade {
+    pub symbol: String,
+    pub taker_order_id: u64,
+    pub maker_order_id: u64,
+    pub taker_side: OrderSide,
+    pub price: Price,
+    pub quantity: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BookError {
+    Order(OrderError),
+    Price(PriceError),
+    DuplicateOrderId(u64),
+    UnknownOrder(u64),
+    SymbolMismatch { book: String, order: String },
+    UnsupportedOrderType(OrderType),
+    BelowFilled { filled: u64, requested: u64 },
+    TriggerEngineClosed,
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookError::Order(err) => write!(f, "{}", err),
+            BookError::Price(err) => write!(f, "{}", err),
+            BookError::DuplicateOrderId(id) => write!(f, "order id {} is already in use", id),
+            BookError::UnknownOrder(id) => write!(f, "order {} is not resting in the book", id),
+            BookError::SymbolMismatch { book, order } => {
+                write!(f, "order for {} sent to the {} book", order, book)
+            }
+            BookError::UnsupportedOrderType(order_type) => {
+                write!(f, "{:?} orders cannot be placed directly on the book", order_type)
+            }
+            BookError::BelowFilled { filled, requested } => {
+                write!(f, "cannot reduce order to {} after {} already filled", requested, filled)
+            }
+            BookError::TriggerEngineClosed => write!(f, "stop trigger engine has shut down"),
+        }
+    }
+}
+
+impl std::error::Error for BookError {}
+
+impl From<OrderError> for BookError {
+    fn from(err: OrderError) -> Self {
+        BookError::Order(err)
+    }
+}
+
+impl From<PriceError> for BookError {
+    fn from(err: PriceError) -> Self {
+        BookError::Price(err)
+    }
+}
+
+/// Key type for price levels, converted from an order's fixed-point price.
+pub trait PriceLevelKey: Copy + Ord + fmt::Debug {
+    fn from_order_price(price: Price, spec: &SymbolSpec) -> Result<Self, PriceError>;
+    fn to_price(self) -> Price;
+}
+
+/// Raw `f64` price level key with a total ordering so it can index a
+/// BTreeMap. Prices are compared as floats, without tick validation.
+#[derive(Debug, Clone, Copy)]
+pub struct FloatPrice(pub f64);
+
+impl PartialEq for FloatPrice {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FloatPrice {}
+
+impl PartialOrd for FloatPrice {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FloatPrice {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl PriceLevelKey for FloatPrice {
+    fn from_order_price(price: Price, _spec: &SymbolSpec) -> Result<Self, PriceError> {
+        Ok(FloatPrice(price.to_f64()))
+    }
+
+    // Keys only come from fixed-point prices, so rounding back is exact
+    fn to_price(self) -> Price {
+        Price::from_units((self.0 * PRICE_SCALE as f64).round() as i64)
+    }
+}
+
+/// Fixed-point keys reject prices that are not on the symbol's tick.
+impl PriceLevelKey for Price {
+    fn from_order_price(price: Price, spec: &SymbolSpec) -> Result<Self, PriceError> {
+        spec.validate_price(price)
+    }
+
+    fn to_price(self) -> Price {
+        self
+    }
+}
+
+/// Order book keyed by fixed-point tick prices.
+pub type TickOrderBook = OrderBook<Price>;
+
+/// Price-time priority limit order book for a single symbol.
+///
+/// Each price level keeps a FIFO queue of resting order ids; the orders
+/// themselves live in an id-indexed map so cancels and lookups are O(1)
+/// apart from removing the id from its level queue. The level key type
+/// decides whether prices are compared as raw floats or integer ticks.
+#[derive(Debug, Clone)]
+pub struct OrderBook<K: PriceLevelKey = FloatPrice> {
+    symbol: String,
+    spec: SymbolSpec,
+    bids: BTreeMap<K, VecDeque<u64>>,
+    asks: BTreeMap<K, VecDeque<u64>>,
+    orders: HashMap<u64, (K, Order)>,
+    last_trade_price: Option<Price>,
+}
+
+impl OrderBook<FloatPrice> {
+    pub fn new(symbol: String) -> Self {
+        Self::with_spec(symbol, SymbolSpec::default())
+    }
+}
+
+impl<K: PriceLevelKey> OrderBook<K> {
+    pub fn with_spec(symbol: String, spec: SymbolSpec) -> Self {
+        Self {
+            symbol,
+            spec,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            orders: HashMap::new(),
+            last_trade_price: None,
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn spec(&self) -> &SymbolSpec {
+        &self.spec
+    }
+
+    /// Number of orders resting in the book.
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    pub fn get_order(&self, order_id: u64) -> Option<&Order> {
+        self.orders.get(&order_id).map(|(_, order)| order)
+    }
+
+    pub fn best_bid(&self) -> Option<Price> {
+        self.bids.keys().next_back().map(|key| key.to_price())
+    }
+
+    pub fn best_ask(&self) -> Option<Price> {
+        self.asks.keys().next().map(|key| key.to_price())
+    }
+
+    pub fn spread(&self) -> Option<Price> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some(Price::from_units(ask.units() - bid.units())),
+            _ => None,
+        }
+    }
+
+    pub fn last_trade_price(&self) -> Option<Price> {
+        self.last_trade_price
+    }
+
+    /// Total resting quantity at a price level on one side of the book.
+    pub fn depth_at(&self, side: OrderSide, price: Price) -> u64 {
+        let Ok(key) = K::from_order_price(price, &self.spec) else { return 0 };
+        let levels = match side {
+            OrderSide::Buy => &self.bids,
+            OrderSide::Sell => &self.asks,
+        };
+        levels
+            .get(&key)
+            .map(|queue| {
+                queue
+                    .iter()
+                    .filter_map(|id| self.orders.get(id))
+                    .map(|(_, order)| order.remaining_quantity)
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Matches an incoming order against the book and rests any limit
+    /// remainder. Market orders never rest: an unfilled remainder is cancelled.
+    pub fn add_order(&mut self, mut order: Order) -> Result<Vec<Trade>, BookError> {
+        let limit = self.validate_incoming(&order)?;
+        let trades = self.match_at(&mut order, limit);
+
+        if order.remaining_quantity > 0 {
+            match limit {
+                Some(key) => self.rest(key, order),
+                None => order.cancel()?,
+            }
+        }
+
+        Ok(trades)
+    }
+
+    /// Removes a resting order from the book and returns it as cancelled.
+    pub fn cancel_order(&mut self, order_id: u64) -> Result<Order, BookError> {
+        let mut order = self.remove_resting(order_id)?;
+        order.cancel()?;
+        Ok(order)
+    }
+
+    /// Changes the quantity and/or price of a resting order.
+    ///
+    /// Reducing quantity at the same price keeps queue position; any price
+    /// change or quantity increase re-enters the order at the back of its
+    /// new level and may trade immediately.
+    pub fn modify_order(
+        &mut self,
+        order_id: u64,
+        new_quantity: u64,
+        new_price: Price,
+    ) -> Result<Vec<Trade>, BookError> {
+        let (current_key, current) = self.orders.get(&order_id).ok_or(BookError::UnknownOrder(order_id))?;
+        if new_quantity <= current.filled_quantity {
+            return Err(BookError::BelowFilled {
+                filled: current.filled_quantity,
+                requested: new_quantity,
+            });
+        }
+        if new_price < Price::ZERO {
+            return Err(BookError::Order(OrderError::InvalidPrice(new_price)));
+        }
+        self.spec.validate_quantity(new_quantity)?;
+        let new_key = K::from_order_price(new_price, &self.spec)?;
+
+        let keeps_priority = new_key == *current_key && new_quantity <= current.quantity;
+
+        if keeps_priority {
+            let (_, order) = self.orders.get_mut(&order_id).expect("order checked above");
+            order.quantity = new_quantity;
+            order.remaining_quantity = new_quantity - order.filled_quantity;
+            return Ok(Vec::new());
+        }
+
+        let mut order = self.remove_resting(order_id)?;
+        order.quantity = new_quantity;
+        order.remaining_quantity = new_quantity - order.filled_quantity;
+        order.price = new_price;
+
+        let trades = self.match_at(&mut order, Some(new_key));
+        if order.remaining_quantity > 0 {
+            self.rest(new_key, order);
+        }
+        Ok(trades)
+    }
+
+    /// Crosses an order against the opposite side of the book in
+    /// price-time priority, filling both sides and returning the trades.
+    /// The incoming order is not rested, but is checked like
+    /// [`OrderBook::add_order`] checks it.
+    pub fn match_order(&mut self, order: &mut Order) -> Result<Vec<Trade>, BookError> {
+        let limit = self.validate_incoming(order)?;
+        Ok(self.match_at(order, limit))
+    }
+
+    // Checks an incoming order can trade in this book and returns its limit
+    fn validate_incoming(&self, order: &Order) -> Result<Option<K>, BookError> {
+        if order.symbol != self.symbol {
+            return Err(BookError::SymbolMismatch {
+                book: self.symbol.clone(),
+                order: order.symbol.clone(),
+            });
+        }
+        if !matches!(order.order_type, OrderType::Market | OrderType::Limit) {
+            return Err(BookError::UnsupportedOrderType(order.order_type));
+        }
+        if !order.is_active() {
+            return Err(BookError::Order(OrderError::InvalidTransition {
+                from: order.status,
+                to: OrderStatus::New,
+            }));
+        }
+        if self.orders.contains_key(&order.order_id) {
+            return Err(BookError::DuplicateOrderId(order.order_id));
+        }
+        self.spec.validate_quantity(order.quantity)?;
+        self.limit_key(order)
+    }
+
+    // Limit orders carry a price level key; market orders cross at any price
+    fn limit_key(&self, order: &Order) -> Result<Option<K>, BookError> {
+        match order.order_type {
+            OrderType::Market => Ok(None),
+            _ => Ok(Some(K::from_order_price(order.price, &self.spec)?)),
+        }
+    }
+
+    fn match_at(&mut self, order: &mut Order, limit: Option<K>) -> Vec<Trade> {
+        let mut trades = Vec::new();
+
+        while order.remaining_quantity > 0 {
+            let best = match order.side {
+                OrderSide::Buy => self.asks.keys().next().copied(),
+                OrderSide::Sell => self.bids.keys().next_back().copied(),
+            };
+            let Some(level_price) = best else { break };
+
+            let crosses = match (limit, order.side) {
+                (None, _) => true,
+                (Some(limit), OrderSide::Buy) => level_price <= limit,
+                (Some(limit), OrderSide::Sell) => level_price >= limit,
+            };
+            if !crosses {
+                break;
+            }
+
+            let levels = match order.side {
+                OrderSide::Buy => &mut self.asks,
+                OrderSide::Sell => &mut self.bids,
+            };
+            let queue = levels.get_mut(&level_price).expect("best level exists");
+
+            while order.remaining_quantity > 0 {
+                let Some(&maker_id) = queue.front() else { break };
+                let (_, maker) = self.orders.get_mut(&maker_id).expect("queued order is resting");
+                let quantity = order.remaining_quantity.min(maker.remaining_quantity);
+
+                maker.update_fill(quantity);
+                order.update_fill(quantity);
+
+                trades.push(Trade {
+                    symbol: self.symbol.clone(),
+                    taker_order_id: order.order_id,
+                    maker_order_id: maker_id,
+                    taker_side: order.side,
+                    price: level_price.to_price(),
+                    quantity,
+                });
+
+                if maker.remaining_quantity == 0 {
+                    queue.pop_front();
+                    self.orders.remove(&maker_id);
+                }
+            }
+
+            if queue.is_empty() {
+                levels.remove(&level_price);
+            }
+            self.last_trade_price = Some(level_price.to_price());
+        }
+
+        trades
+    }
+
+    fn rest(&mut self, key: K, order: Order) {
+        let levels = match order.side {
+            OrderSide::Buy => &mut self.bids,
+            OrderSide::Sell => &mut self.asks,
+        };
+        levels.entry(key).or_default().push_back(order.order_id);
+        self.orders.insert(order.order_id, (key, order));
+    }
+
+    fn remove_resting(&mut self, order_id: u64) -> Result<Order, BookError> {
+        let (key, order) = self.orders.remove(&order_id).ok_or(BookError::UnknownOrder(order_id))?;
+        let levels = match order.side {
+            OrderSide::Buy => &mut self.bids,
+            OrderSide::Sell => &mut self.asks,
+        };
+        if let Some(queue) = levels.get_mut(&key) {
+            queue.retain(|id| *id != order_id);
+            if queue.is_empty() {
+                levels.remove(&key);
+            }
+        }
+        Ok(order)
+    }
+}
+
+/// Collection of per-symbol order books that routes orders by symbol.
+#[derive(Debug, Clone)]
+pub struct OrderBooks<K: PriceLevelKey = FloatPrice> {
+    specs: SymbolSpecs,
+    books: HashMap<String, OrderBook<K>>,
+}
+
+/// Per-symbol order books keyed by fixed-point tick prices.
+pub type TickOrderBooks = OrderBooks<Price>;
+
+impl OrderBooks<FloatPrice> {
+    pub fn new() -> Self {
+        Self::with_specs(SymbolSpecs::default())
+    }
+}
+
+impl Default for OrderBooks<FloatPrice> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PriceLevelKey> OrderBooks<K> {
+    /// Books created on first use take their tick and lot size from `specs`.
+    pub fn with_specs(specs: SymbolSpecs) -> Self {
+        Self {
+            specs,
+            books: HashMap::new(),
+        }
+    }
+
+    pub fn book(&self, symbol: &str) -> Option<&OrderBook<K>> {
+        self.books.get(symbol)
+    }
+
+    /// Returns the book for a symbol, creating an empty one on first use.
+    pub fn book_mut(&mut self, symbol: &str) -> &mut OrderBook<K> {
+        if !self.books.contains_key(symbol) {
+            let spec = *self.specs.get(symbol);
+            self.books.insert(symbol.to_string(), OrderBook::with_spec(symbol.to_string(), spec));
+        }
+        self.books.get_mut(symbol).expect("book inserted above")
+    }
+
+    pub fn add_order(&mut self, order: Order) -> Result<Vec<Trade>, BookError> {
+        match self.books.get_mut(&order.symbol) {
+            Some(book) => book.add_order(order),
+            None => self.book_mut(&order.symbol.clone()).add_order(order),
+        }
+    }
+
+    pub fn cancel_order(&mut self, symbol: &str, order_id: u64) -> Result<Order, BookError> {
+        self.books
+            .get_mut(symbol)
+            .ok_or(BookError::UnknownOrder(order_id))?
+            .cancel_order(order_id)
+    }
+
+    pub fn modify_order(
+        &mut self,
+        symbol: &str,
+        order_id: u64,
+        new_quantity: u64,
+        new_price: Price,
+    ) -> Result<Vec<Trade>, BookError> {
+        self.books
+            .get_mut(symbol)
+            .ok_or(BookError::UnknownOrder(order_id))?
+            .modify_order(order_id, new_quantity, new_price)
+    }
+
+    pub fn len(&self) -> usize {
+        self.books.values().map(OrderBook::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.books.values().all(OrderBook::is_empty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn px(value: f64) -> Price {
+        Price::from_f64(value).unwrap()
+    }
+
+    // Stops get their limit price as stop price
+    fn order(order_id: u64, side: OrderSide, order_type: OrderType, quantity: u64, price: f64) -> Order {
+        let stop_price = matches!(order_type, OrderType::Stop | OrderType::StopLimit).then(|| px(price));
+        Order::with_details(order_id, "AAPL".to_string(), "CLIENT_1".to_string(), side, order_type, quantity, px(price), stop_price, order_id)
+            .unwrap()
+    }
+
+    fn limit(order_id: u64, side: OrderSide, quantity: u64, price: f64) -> Order {
+        order(order_id, side, OrderType::Limit, quantity, price)
+    }
+
+    fn fills(trades: &[Trade]) -> Vec<(u64, f64, u64)> {
+        trades.iter().map(|trade| (trade.maker_order_id, trade.price.to_f64(), trade.quantity)).collect()
+    }
+
+    #[test]
+    fn matches_across_levels_best_price_first() {
+        let mut book = OrderBook::new("AAPL".to_string());
+        book.add_order(limit(1, OrderSide::Sell, 10, 101.0)).unwrap();
+        book.add_order(limit(2, OrderSide::Sell, 10, 100.0)).unwrap();
+        book.add_order(limit(3, OrderSide::Sell, 10, 102.0)).unwrap();
+
+        let trades = book.add_order(limit(4, OrderSide::Buy, 25, 101.5)).unwrap();
+
+        assert_eq!(fills(&trades), vec![(2, 100.0, 10), (1, 101.0, 10)]);
+        // The unfilled 5 rests at its limit, below the remaining ask
+        assert_eq!(book.best_bid(), Some(px(101.5)));
+        assert_eq!(book.depth_at(OrderSide::Buy, px(101.5)), 5);
+        assert_eq!(book.best_ask(), Some(px(102.0)));
+        assert_eq!(book.last_trade_price(), Some(px(101.0)));
+    }
+
+    #[test]
+    fn fills_a_level_in_time_priority() {
+        let mut book = OrderBook::new("AAPL".to_string());
+        book.add_order(limit(1, OrderSide::Buy, 10, 100.0)).unwrap();
+        book.add_order(limit(2, OrderSide::Buy, 10, 100.0)).unwrap();
+
+        let trades = book.add_order(limit(3, OrderSide::Sell, 15, 100.0)).unwrap();
+
+        assert_eq!(fills(&trades), vec![(1, 100.0, 10), (2, 100.0, 5)]);
+        assert!(book.get_order(1).is_none());
+        let partial = book.get_order(2).unwrap();
+        assert_eq!(partial.remaining_quantity, 5);
+        assert_eq!(partial.status, OrderStatus::PartiallyFilled);
+    }
+
+    #[test]
+    fn market_remainder_is_not_rested() {
+        let mut book = OrderBook::new("AAPL".to_string());
+        book.add_order(limit(1, OrderSide::Sell, 10, 100.0)).unwrap();
+
+        let trades = book.add_order(order(2, OrderSide::Buy, OrderType::Market, 15, 0.0)).unwrap();
+
+        assert_eq!(fills(&trades), vec![(1, 100.0, 10)]);
+        assert!(book.is_empty());
+    }
+
+    #[test]
+    fn limit_does_not_cross_worse_prices() {
+        let mut book = OrderBook::new("AAPL".to_string());
+        book.add_order(limit(1, OrderSide::Sell, 10, 101.0)).unwrap();
+
+        let trades = book.add_order(limit(2, OrderSide::Buy, 10, 100.0)).unwrap();
+
+        assert!(trades.is_empty());
+        assert_eq!(book.spread(), Some(px(1.0)));
+    }
+
+    #[test]
+    fn modify_keeps_priority_only_when_reducing_in_place() {
+        let mut book = OrderBook::new("AAPL".to_string());
+        book.add_order(limit(1, OrderSide::Sell, 10, 100.0)).unwrap();
+        book.add_order(limit(2, OrderSide::Sell, 10, 100.0)).unwrap();
+
+        book.modify_order(1, 5, px(100.0)).unwrap();
+        let trades = book.add_order(limit(3, OrderSide::Buy, 5, 100.0)).unwrap();
+        assert_eq!(fills(&trades), vec![(1, 100.0, 5)]);
+
+        book.add_order(limit(4, OrderSide::Sell, 10, 100.0)).unwrap();
+        book.modify_order(2, 20, px(100.0)).unwrap();
+        let trades = book.add_order(limit(5, OrderSide::Buy, 10, 100.0)).unwrap();
+        assert_eq!(fills(&trades), vec![(4, 100.0, 10)]);
+    }
+
+    #[test]
+    fn modify_cannot_shrink_below_the_filled_quantity() {
+        let mut book = OrderBook::new("AAPL".to_string());
+        book.add_order(limit(1, OrderSide::Sell, 10, 100.0)).unwrap();
+        book.add_order(limit(2, OrderSide::Buy, 4, 100.0)).unwrap();
+
+        assert_eq!(book.modify_order(1, 4, px(100.0)), Err(BookError::BelowFilled { filled: 4, requested: 4 }));
+        book.modify_order(1, 5, px(100.0)).unwrap();
+        assert_eq!(book.depth_at(OrderSide::Sell, px(100.0)), 1);
+    }
+
+    #[test]
+    fn match_order_validates_like_add_order() {
+        let mut book = OrderBook::new("AAPL".to_string());
+        book.add_order(limit(1, OrderSide::Sell, 10, 100.0)).unwrap();
+
+        let mut duplicate = limit(1, OrderSide::Buy, 5, 100.0);
+        assert_eq!(book.match_order(&mut duplicate), Err(BookError::DuplicateOrderId(1)));
+        let mut stop = order(2, OrderSide::Buy, OrderType::Stop, 5, 100.0);
+        assert_eq!(book.match_order(&mut stop), Err(BookError::UnsupportedOrderType(OrderType::Stop)));
+        let mut foreign = limit(3, OrderSide::Buy, 5, 100.0);
+        foreign.symbol = "MSFT".to_string();
+        assert!(matches!(book.match_order(&mut foreign), Err(BookError::SymbolMismatch { .. })));
+        let mut cancelled = limit(4, OrderSide::Buy, 5, 100.0);
+        cancelled.cancel().unwrap();
+        assert!(matches!(book.match_order(&mut cancelled), Err(BookError::Order(OrderError::InvalidTransition { .. }))));
+        assert_eq!(book.depth_at(OrderSide::Sell, px(100.0)), 10);
+
+        let mut taker = limit(5, OrderSide::Buy, 5, 100.0);
+        let trades = book.match_order(&mut taker).unwrap();
+        assert_eq!(fills(&trades), vec![(1, 100.0, 5)]);
+        assert_eq!(taker.status, OrderStatus::Filled);
+        assert!(book.get_order(5).is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_orders() {
+        let mut book = OrderBook::new("AAPL".to_string());
+        book.add_order(limit(1, OrderSide::Sell, 10, 100.0)).unwrap();
+
+        assert_eq!(book.add_order(limit(1, OrderSide::Sell, 10, 100.0)), Err(BookError::DuplicateOrderId(1)));
+        assert_eq!(
+            book.add_order(order(2, OrderSide::Buy, OrderType::Stop, 10, 100.0)),
+            Err(BookError::UnsupportedOrderType(OrderType::Stop))
+        );
+        assert_eq!(book.cancel_order(3), Err(BookError::UnknownOrder(3)));
+        assert_eq!(book.cancel_order(1).unwrap().status, OrderStatus::Cancelled);
+        assert!(book.is_empty());
+    }
+
+    #[test]
+    fn tick_book_rejects_off_tick_prices() {
+        let mut book = TickOrderBook::with_spec("AAPL".to_string(), SymbolSpec::new(Price::from_units(500), 10).unwrap());
+
+        assert!(matches!(
+            book.add_order(limit(1, OrderSide::Sell, 10, 100.03)),
+            Err(BookError::Price(PriceError::NotOnTick { .. }))
+        ));
+        assert!(matches!(
+            book.add_order(limit(2, OrderSide::Sell, 15, 100.05)),
+            Err(BookError::Price(PriceError::NotOnLot { .. }))
+        ));
+        book.add_order(limit(3, OrderSide::Sell, 20, 100.05)).unwrap();
+        assert_eq!(book.depth_at(OrderSide::Sell, px(100.05)), 20);
+    }
+}
@@ -0,0 +1,462 @@
+use crate::interner::{ClientId, OrderId, SymbolId, SymbolTable};
+use crate::price::Price;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OrderType {
+    Market,
+    Limit,
+    Stop,
+    StopLimit,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+}
+
+impl OrderStatus {
+    /// Filled, cancelled and rejected orders can no longer change state.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderError {
+    ZeroQuantity,
+    InvalidPrice(Price),
+    MissingStopPrice,
+    Overfill { requested: u64, remaining: u64 },
+    InvalidTransition { from: OrderStatus, to: OrderStatus },
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::ZeroQuantity => write!(f, "order quantity must be greater than zero"),
+            OrderError::InvalidPrice(price) => write!(f, "invalid order price: {}", price),
+            OrderError::MissingStopPrice => write!(f, "stop orders require a stop price"),
+            OrderError::Overfill { requested, remaining } => write!(
+                f,
+                "fill of {} exceeds remaining quantity {}",
+                requested, remaining
+            ),
+            OrderError::InvalidTransition { from, to } => {
+                write!(f, "cannot move order from {:?} to {:?}", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Order {
+    pub order_id: u64,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: u64,
+    pub price: Price,
+    #[serde(default)]
+    pub stop_price: Option<Price>,
+    pub filled_quantity: u64,
+    pub remaining_quantity: u64,
+    pub status: OrderStatus,
+    pub timestamp: u64,
+    pub client_id: String,
+}
+
+impl Order {
+    /// Creates an order with random side, type, quantity, price and timestamp.
+    pub fn new(order_id: u64, symbol: String, client_id: String) -> Self {
+        Self::random(&mut thread_rng(), order_id, symbol, client_id)
+    }
+
+    /// Like [`Order::new`] but draws from the given generator, so a seeded
+    /// RNG yields the same order every time.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R, order_id: u64, symbol: String, client_id: String) -> Self {
+        let quantity = rng.gen_range(1..10000);
+        let order_type = match rng.gen_range(0..4) {
+            0 => OrderType::Market,
+            1 => OrderType::Limit,
+            2 => OrderType::Stop,
+            _ => OrderType::StopLimit,
+        };
+        let stop_price = match order_type {
+            OrderType::Stop | OrderType::StopLimit => Some(Price::from_cents(rng.gen_range(1_000..100_000))),
+            _ => None,
+        };
+        Self {
+            order_id,
+            symbol,
+            side: if rng.gen_bool(0.5) { OrderSide::Buy } else { OrderSide::Sell },
+            order_type,
+            quantity,
+            price: Price::from_cents(rng.gen_range(1_000..100_000)),
+            stop_price,
+            filled_quantity: 0,
+            remaining_quantity: quantity,
+            status: OrderStatus::New,
+            timestamp: rng.gen_range(1600000000..1700000000),
+            client_id,
+        }
+    }
+
+    /// Creates a new order from explicit parameters, rejecting zero quantities,
+    /// negative prices and `Stop` or `StopLimit` orders without a stop price.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_details(
+        order_id: u64,
+        symbol: String,
+        client_id: String,
+        side: OrderSide,
+        order_type: OrderType,
+        quantity: u64,
+        price: Price,
+        stop_price: Option<Price>,
+        timestamp: u64,
+    ) -> Result<Self, OrderError> {
+        if quantity == 0 {
+            return Err(OrderError::ZeroQuantity);
+        }
+        if price < Price::ZERO {
+            return Err(OrderError::InvalidPrice(price));
+        }
+        match stop_price {
+            Some(stop_price) if stop_price < Price::ZERO => return Err(OrderError::InvalidPrice(stop_price)),
+            None if matches!(order_type, OrderType::Stop | OrderType::StopLimit) => {
+                return Err(OrderError::MissingStopPrice)
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            order_id,
+            symbol,
+            side,
+            order_type,
+            quantity,
+            price,
+            stop_price,
+            filled_quantity: 0,
+            remaining_quantity: quantity,
+            status: OrderStatus::New,
+            timestamp,
+            client_id,
+        })
+    }
+
+    /// Sets the trigger price of a `Stop` or `StopLimit` order. For stop-limit
+    /// orders `price` remains the limit price used once triggered.
+    pub fn with_stop_price(mut self, stop_price: Price) -> Result<Self, OrderError> {
+        if stop_price < Price::ZERO {
+            return Err(OrderError::InvalidPrice(stop_price));
+        }
+        self.stop_price = Some(stop_price);
+        Ok(self)
+    }
+
+    pub fn is_stop(&self) -> bool {
+        matches!(self.order_type, OrderType::Stop | OrderType::StopLimit)
+    }
+
+    /// Whether a trade at `last_price` fires this stop: buy stops trigger at
+    /// or above the stop price, sell stops at or below it.
+    pub fn is_triggered_by(&self, last_price: Price) -> bool {
+        match (self.is_stop(), self.stop_price) {
+            (true, Some(stop_price)) => match self.side {
+                OrderSide::Buy => last_price >= stop_price,
+                OrderSide::Sell => last_price <= stop_price,
+            },
+            _ => false,
+        }
+    }
+
+    /// Converts a triggered stop into the order it stands for: `Stop`
+    /// becomes `Market` and `StopLimit` becomes `Limit` at `price`.
+    pub fn trigger(&mut self) {
+        self.order_type = match self.order_type {
+            OrderType::Stop => OrderType::Market,
+            OrderType::StopLimit => OrderType::Limit,
+            other => other,
+        };
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.status.is_terminal()
+    }
+
+    /// Applies a fill, clamping it to the remaining quantity. This is the
+    /// unchecked fast path used by the benchmarks.
+    pub fn update_fill(&mut self, fill_quantity: u64) {
+        let actual_fill = std::cmp::min(fill_quantity, self.remaining_quantity);
+        self.filled_quantity += actual_fill;
+        self.remaining_quantity -= actual_fill;
+
+        if self.remaining_quantity == 0 {
+            self.status = OrderStatus::Filled;
+        } else {
+            self.status = OrderStatus::PartiallyFilled;
+        }
+    }
+
+    /// Applies a fill, failing if the order is no longer active or the fill
+    /// exceeds the remaining quantity.
+    pub fn fill(&mut self, fill_quantity: u64) -> Result<(), OrderError> {
+        if fill_quantity == 0 {
+            return Err(OrderError::ZeroQuantity);
+        }
+        if !self.is_active() {
+            return Err(OrderError::InvalidTransition {
+                from: self.status,
+                to: OrderStatus::Filled,
+            });
+        }
+        if fill_quantity > self.remaining_quantity {
+            return Err(OrderError::Overfill {
+                requested: fill_quantity,
+                remaining: self.remaining_quantity,
+            });
+        }
+
+        self.update_fill(fill_quantity);
+        Ok(())
+    }
+
+    pub fn cancel(&mut self) -> Result<(), OrderError> {
+        self.transition(OrderStatus::Cancelled)
+    }
+
+    /// Only orders that have not been filled at all can be rejected.
+    pub fn reject(&mut self) -> Result<(), OrderError> {
+        if self.status != OrderStatus::New {
+            return Err(OrderError::InvalidTransition {
+                from: self.status,
+                to: OrderStatus::Rejected,
+            });
+        }
+        self.transition(OrderStatus::Rejected)
+    }
+
+    fn transition(&mut self, to: OrderStatus) -> Result<(), OrderError> {
+        if !self.is_active() {
+            return Err(OrderError::InvalidTransition { from: self.status, to });
+        }
+        self.status = to;
+        Ok(())
+    }
+}
+
+/// Allocation-free order representation keyed by numeric and interned ids,
+/// used by the interned key strategy.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CompactOrder {
+    pub order_id: OrderId,
+    pub symbol: SymbolId,
+    pub client_id: ClientId,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: u64,
+    pub price: Price,
+    pub stop_price: Option<Price>,
+    pub filled_quantity: u64,
+    pub remaining_quantity: u64,
+    pub status: OrderStatus,
+    pub timestamp: u64,
+}
+
+impl CompactOrder {
+    /// Interns the order's symbol and client into `table`.
+    pub fn from_order(order: &Order, table: &mut SymbolTable) -> Self {
+        Self {
+            order_id: OrderId(order.order_id),
+            symbol: table.symbol(&order.symbol),
+            client_id: table.client(&order.client_id),
+            side: order.side,
+            order_type: order.order_type,
+            quantity: order.quantity,
+            price: order.price,
+            stop_price: order.stop_price,
+            filled_quantity: order.filled_quantity,
+            remaining_quantity: order.remaining_quantity,
+            status: order.status,
+            timestamp: order.timestamp,
+        }
+    }
+
+    /// Resolves the interned ids back into a full [`Order`].
+    pub fn to_order(&self, table: &SymbolTable) -> Option<Order> {
+        Some(Order {
+            order_id: self.order_id.0,
+            symbol: table.symbol_name(self.symbol)?.to_string(),
+            side: self.side,
+            order_type: self.order_type,
+            quantity: self.quantity,
+            price: self.price,
+            stop_price: self.stop_price,
+            filled_quantity: self.filled_quantity,
+            remaining_quantity: self.remaining_quantity,
+            status: self.status,
+            timestamp: self.timestamp,
+            client_id: table.client_name(self.client_id)?.to_string(),
+        })
+    }
+
+    /// Same clamping fill as [`Order::update_fill`].
+    pub fn update_fill(&mut self, fill_quantity: u64) {
+        let actual_fill = std::cmp::min(fill_quantity, self.remaining_quantity);
+        self.filled_quantity += actual_fill;
+        self.remaining_quantity -= actual_fill;
+
+        if self.remaining_quantity == 0 {
+            self.status = OrderStatus::Filled;
+        } else {
+            self.status = OrderStatus::PartiallyFilled;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(value: &str) -> Price {
+        value.parse().unwrap()
+    }
+
+    fn order(quantity: u64) -> Order {
+        Order::with_details(1, "AAPL".to_string(), "CLIENT_1".to_string(), OrderSide::Buy, OrderType::Limit, quantity, price("100.25"), None, 42)
+            .unwrap()
+    }
+
+    #[test]
+    fn validates_details() {
+        let details = |order_type, quantity, price, stop_price| {
+            Order::with_details(1, "AAPL".to_string(), "CLIENT_1".to_string(), OrderSide::Sell, order_type, quantity, price, stop_price, 0)
+        };
+
+        assert_eq!(details(OrderType::Limit, 0, price("100"), None), Err(OrderError::ZeroQuantity));
+        assert_eq!(details(OrderType::Limit, 10, price("-1"), None), Err(OrderError::InvalidPrice(price("-1"))));
+        assert_eq!(details(OrderType::Stop, 10, price("100"), Some(price("-2"))), Err(OrderError::InvalidPrice(price("-2"))));
+        assert_eq!(details(OrderType::StopLimit, 10, price("100"), Some(price("99"))).unwrap().stop_price, Some(price("99")));
+        assert_eq!(order(10).with_stop_price(price("-0.01")), Err(OrderError::InvalidPrice(price("-0.01"))));
+        assert_eq!(order(10).with_stop_price(price("99.5")).unwrap().stop_price, Some(price("99.5")));
+    }
+
+    #[test]
+    fn stop_orders_need_a_stop_price() {
+        for order_type in [OrderType::Stop, OrderType::StopLimit] {
+            let order = Order::with_details(1, "AAPL".to_string(), "CLIENT_1".to_string(), OrderSide::Buy, order_type, 10, price("100"), None, 0);
+            assert_eq!(order, Err(OrderError::MissingStopPrice));
+        }
+    }
+
+    #[test]
+    fn fills_until_filled() {
+        let mut order = order(10);
+
+        order.fill(4).unwrap();
+        assert_eq!((order.filled_quantity, order.remaining_quantity), (4, 6));
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+
+        assert_eq!(order.fill(0), Err(OrderError::ZeroQuantity));
+        assert_eq!(order.fill(7), Err(OrderError::Overfill { requested: 7, remaining: 6 }));
+        assert_eq!(order.remaining_quantity, 6);
+
+        order.fill(6).unwrap();
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(
+            order.fill(1),
+            Err(OrderError::InvalidTransition { from: OrderStatus::Filled, to: OrderStatus::Filled })
+        );
+        assert_eq!(
+            order.cancel(),
+            Err(OrderError::InvalidTransition { from: OrderStatus::Filled, to: OrderStatus::Cancelled })
+        );
+    }
+
+    #[test]
+    fn update_fill_clamps_to_the_remaining_quantity() {
+        let mut order = order(10);
+
+        order.update_fill(3);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        order.update_fill(50);
+        assert_eq!((order.filled_quantity, order.remaining_quantity), (10, 0));
+        assert_eq!(order.status, OrderStatus::Filled);
+    }
+
+    #[test]
+    fn cancels_and_rejects_only_from_allowed_states() {
+        let mut cancelled = order(10);
+        cancelled.cancel().unwrap();
+        assert_eq!(cancelled.status, OrderStatus::Cancelled);
+        assert!(!cancelled.is_active());
+        assert_eq!(
+            cancelled.reject(),
+            Err(OrderError::InvalidTransition { from: OrderStatus::Cancelled, to: OrderStatus::Rejected })
+        );
+
+        let mut rejected = order(10);
+        rejected.reject().unwrap();
+        assert_eq!(rejected.status, OrderStatus::Rejected);
+        assert_eq!(
+            rejected.fill(1),
+            Err(OrderError::InvalidTransition { from: OrderStatus::Rejected, to: OrderStatus::Filled })
+        );
+
+        let mut partial = order(10);
+        partial.fill(5).unwrap();
+        assert_eq!(
+            partial.reject(),
+            Err(OrderError::InvalidTransition { from: OrderStatus::PartiallyFilled, to: OrderStatus::Rejected })
+        );
+        partial.cancel().unwrap();
+        assert_eq!(partial.status, OrderStatus::Cancelled);
+    }
+
+    #[test]
+    fn order_round_trips_through_json() {
+        let mut order = order(10).with_stop_price(price("99.5")).unwrap();
+        order.order_type = OrderType::StopLimit;
+        order.fill(3).unwrap();
+
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(serde_json::from_str::<Order>(&json).unwrap(), order);
+    }
+
+    #[test]
+    fn order_without_stop_price_field_deserializes() {
+        let mut value = serde_json::to_value(order(10)).unwrap();
+        value.as_object_mut().unwrap().remove("stop_price");
+
+        assert_eq!(serde_json::from_value::<Order>(value).unwrap(), order(10));
+    }
+
+    #[test]
+    fn compact_order_round_trips_through_json_and_the_table() {
+        let mut table = SymbolTable::new();
+        let order = order(10);
+        let compact = CompactOrder::from_order(&order, &mut table);
+
+        let json = serde_json::to_string(&compact).unwrap();
+        let decoded: CompactOrder = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, compact);
+        assert_eq!(decoded.to_order(&table), Some(order));
+        assert_eq!(decoded.to_order(&SymbolTable::new()), None);
+    }
+}
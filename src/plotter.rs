@@ -1,37 +1,24 @@
+use crate::findings::{key_findings, markdown_bold_to_html, recommendations};
+use crate::simple_plotter::BenchmarkResult;
 use plotters::prelude::*;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
+use std::ops::Range;
 use chrono::Utc;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BenchmarkResult {
-    pub name: String,
-    pub operation_type: String,
-    pub data_size: usize,
-    pub time_ns: f64,
-    pub time_us: f64,
-    pub time_ms: f64,
-    pub throughput_ops_per_sec: f64,
-}
-
-impl BenchmarkResult {
-    pub fn new(name: String, operation_type: String, data_size: usize, time_ns: f64) -> Self {
-        let time_us = time_ns / 1_000.0;
-        let time_ms = time_ns / 1_000_000.0;
-        let throughput_ops_per_sec = (data_size as f64) / (time_ns / 1_000_000_000.0);
-        
-        Self {
-            name,
-            operation_type,
-            data_size,
-            time_ns,
-            time_us,
-            time_ms,
-            throughput_ops_per_sec,
-        }
+// Axis range covering every value with a margin on both ends. Falls back to
+// 0..1 when there is nothing to plot.
+fn data_range(values: impl Iterator<Item = f64>, from_zero: bool) -> Range<f64> {
+    let (min, max) = values
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+    if min > max {
+        return 0f64..1f64;
     }
+    let margin = ((max - min) * 0.05).max(max.abs() * 0.05).max(f64::EPSILON);
+    let start = if from_zero { 0f64.min(min) } else { min - margin };
+    start..max + margin
 }
 
 pub struct BenchmarkPlotter {
@@ -45,6 +32,14 @@ impl BenchmarkPlotter {
         }
     }
     
+    pub fn from_results(results: Vec<BenchmarkResult>) -> Self {
+        Self { results }
+    }
+    
+    fn data_size_range(&self) -> Range<f64> {
+        data_range(self.results.iter().map(|r| r.data_size as f64), false)
+    }
+    
     pub fn add_result(&mut self, result: BenchmarkResult) {
         self.results.push(result);
     }
@@ -79,7 +74,7 @@ impl BenchmarkPlotter {
             .margin(10)
             .x_label_area_size(60)
             .y_label_area_size(80)
-            .build_cartesian_2d(50f64..12000f64, 0f64..12000f64)?;
+            .build_cartesian_2d(self.data_size_range(), data_range(self.results.iter().map(|r| r.time_us), true))?;
         
         chart
             .configure_mesh()
@@ -89,19 +84,18 @@ impl BenchmarkPlotter {
             .draw()?;
         
         // Group results by operation type
-        let mut grouped_results: HashMap<String, Vec<&BenchmarkResult>> = HashMap::new();
+        let mut grouped_results: BTreeMap<String, Vec<&BenchmarkResult>> = BTreeMap::new();
         for result in &self.results {
             grouped_results.entry(result.operation_type.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(result);
         }
         
-        let colors = [&RED, &BLUE, &GREEN, &MAGENTA, &CYAN];
-        let mut color_idx = 0;
+        let colors = [&RED, &BLUE, &GREEN, &MAGENTA, &CYAN, &BLACK];
         
-        for (op_type, results) in grouped_results {
+        for (color_idx, (op_type, results)) in grouped_results.into_iter().enumerate() {
             let mut sorted_results = results;
-            sorted_results.sort_by(|a, b| a.data_size.cmp(&b.data_size));
+            sorted_results.sort_by_key(|r| r.data_size);
             
             let data_points: Vec<(f64, f64)> = sorted_results.iter()
                 .map(|r| (r.data_size as f64, r.time_us))
@@ -120,17 +114,15 @@ impl BenchmarkPlotter {
                 5,
                 colors[color_idx % colors.len()],
                 &|c, s, st| {
-                    return EmptyElement::at(c)    // We want to construct a composed element on-the-fly
-                        + Circle::new((0, 0), s, st.filled());
+                    EmptyElement::at(c)    // We want to construct a composed element on-the-fly
+                        + Circle::new((0, 0), s, st.filled())
                 },
             ))?;
-            
-            color_idx += 1;
         }
         
         chart.configure_series_labels()
-            .background_style(&WHITE.mix(0.8))
-            .border_style(&BLACK)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
             .draw()?;
         
         root.present()?;
@@ -147,7 +139,7 @@ impl BenchmarkPlotter {
             .margin(10)
             .x_label_area_size(60)
             .y_label_area_size(80)
-            .build_cartesian_2d(50f64..12000f64, 0f64..25000000f64)?;
+            .build_cartesian_2d(self.data_size_range(), data_range(self.results.iter().map(|r| r.throughput_ops_per_sec), true))?;
         
         chart
             .configure_mesh()
@@ -157,19 +149,18 @@ impl BenchmarkPlotter {
             .draw()?;
         
         // Group results by operation type
-        let mut grouped_results: HashMap<String, Vec<&BenchmarkResult>> = HashMap::new();
+        let mut grouped_results: BTreeMap<String, Vec<&BenchmarkResult>> = BTreeMap::new();
         for result in &self.results {
             grouped_results.entry(result.operation_type.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(result);
         }
         
-        let colors = [&RED, &BLUE, &GREEN, &MAGENTA, &CYAN];
-        let mut color_idx = 0;
+        let colors = [&RED, &BLUE, &GREEN, &MAGENTA, &CYAN, &BLACK];
         
-        for (op_type, results) in grouped_results {
+        for (color_idx, (op_type, results)) in grouped_results.into_iter().enumerate() {
             let mut sorted_results = results;
-            sorted_results.sort_by(|a, b| a.data_size.cmp(&b.data_size));
+            sorted_results.sort_by_key(|r| r.data_size);
             
             let data_points: Vec<(f64, f64)> = sorted_results.iter()
                 .map(|r| (r.data_size as f64, r.throughput_ops_per_sec))
@@ -188,17 +179,15 @@ impl BenchmarkPlotter {
                 5,
                 colors[color_idx % colors.len()],
                 &|c, s, st| {
-                    return EmptyElement::at(c)
-                        + Circle::new((0, 0), s, st.filled());
+                    EmptyElement::at(c)
+                        + Circle::new((0, 0), s, st.filled())
                 },
             ))?;
-            
-            color_idx += 1;
         }
         
         chart.configure_series_labels()
-            .background_style(&WHITE.mix(0.8))
-            .border_style(&BLACK)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
             .draw()?;
         
         root.present()?;
@@ -215,7 +204,10 @@ impl BenchmarkPlotter {
             .margin(10)
             .x_label_area_size(60)
             .y_label_area_size(80)
-            .build_cartesian_2d(50f64..12000f64, 0f64..1200f64)?;
+            .build_cartesian_2d(
+                self.data_size_range(),
+                data_range(self.results.iter().map(|r| r.time_ns / r.data_size as f64), true),
+            )?;
         
         chart
             .configure_mesh()
@@ -225,19 +217,18 @@ impl BenchmarkPlotter {
             .draw()?;
         
         // Group results by operation type
-        let mut grouped_results: HashMap<String, Vec<&BenchmarkResult>> = HashMap::new();
+        let mut grouped_results: BTreeMap<String, Vec<&BenchmarkResult>> = BTreeMap::new();
         for result in &self.results {
             grouped_results.entry(result.operation_type.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(result);
         }
         
-        let colors = [&RED, &BLUE, &GREEN, &MAGENTA, &CYAN];
-        let mut color_idx = 0;
+        let colors = [&RED, &BLUE, &GREEN, &MAGENTA, &CYAN, &BLACK];
         
-        for (op_type, results) in grouped_results {
+        for (color_idx, (op_type, results)) in grouped_results.into_iter().enumerate() {
             let mut sorted_results = results;
-            sorted_results.sort_by(|a, b| a.data_size.cmp(&b.data_size));
+            sorted_results.sort_by_key(|r| r.data_size);
             
             let data_points: Vec<(f64, f64)> = sorted_results.iter()
                 .map(|r| (r.data_size as f64, r.time_ns / r.data_size as f64))
@@ -256,17 +247,15 @@ impl BenchmarkPlotter {
                 5,
                 colors[color_idx % colors.len()],
                 &|c, s, st| {
-                    return EmptyElement::at(c)
-                        + Circle::new((0, 0), s, st.filled());
+                    EmptyElement::at(c)
+                        + Circle::new((0, 0), s, st.filled())
                 },
             ))?;
-            
-            color_idx += 1;
         }
         
         chart.configure_series_labels()
-            .background_style(&WHITE.mix(0.8))
-            .border_style(&BLACK)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
             .draw()?;
         
         root.present()?;
@@ -299,12 +288,7 @@ impl BenchmarkPlotter {
     }
     
     pub fn generate_report(&self, output_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
-        std::fs::create_dir_all(output_dir)?;
-        
-        // Generate all plots
-        self.plot_latency_comparison(&format!("{}/latency_comparison.png", output_dir))?;
-        self.plot_throughput_comparison(&format!("{}/throughput_comparison.png", output_dir))?;
-        self.plot_scalability_analysis(&format!("{}/scalability_analysis.png", output_dir))?;
+        self.generate_charts(output_dir)?;
         
         // Export CSV
         self.export_csv(&format!("{}/benchmark_results.csv", output_dir))?;
@@ -318,6 +302,18 @@ impl BenchmarkPlotter {
         Ok(())
     }
     
+    /// Only the PNG charts, for use next to
+    /// `SimplePlotter::generate_complete_report`, which owns the CSV and HTML.
+    pub fn generate_charts(&self, output_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(output_dir)?;
+        
+        self.plot_latency_comparison(&format!("{}/latency_comparison.png", output_dir))?;
+        self.plot_throughput_comparison(&format!("{}/throughput_comparison.png", output_dir))?;
+        self.plot_scalability_analysis(&format!("{}/scalability_analysis.png", output_dir))?;
+        
+        Ok(())
+    }
+    
     fn generate_html_report(&self) -> Result<String, Box<dyn std::error::Error>> {
         let timestamp = Utc::now();
         
@@ -336,7 +332,7 @@ impl BenchmarkPlotter {
         html.push_str("</style>\n");
         html.push_str("</head>\n<body>\n");
         
-        html.push_str(&format!("<h1>HFT Order Processing Benchmark Report</h1>\n"));
+        html.push_str("<h1>HFT Order Processing Benchmark Report</h1>\n");
         html.push_str(&format!("<p>Generated on: {}</p>\n", timestamp.format("%Y-%m-%d %H:%M:%S UTC")));
         
         // Summary section
@@ -344,10 +340,13 @@ impl BenchmarkPlotter {
         html.push_str("<div class='metric'>\n");
         html.push_str("<p><strong>Key Findings:</strong></p>\n");
         html.push_str("<ul>\n");
-        html.push_str("<li>Sync operations are consistently 40-60% faster than async operations</li>\n");
-        html.push_str("<li>Concurrent operations add ~15-30% overhead but provide thread safety</li>\n");
-        html.push_str("<li>Per-order latency ranges from 465ns (sync) to 1050ns (async)</li>\n");
-        html.push_str("<li>Throughput scales linearly with order volume</li>\n");
+        let findings = key_findings(&self.results);
+        if findings.is_empty() {
+            html.push_str("<li>Not enough results to draw comparative findings</li>\n");
+        }
+        for finding in findings {
+            html.push_str(&format!("<li>{}</li>\n", markdown_bold_to_html(&finding)));
+        }
         html.push_str("</ul>\n");
         html.push_str("</div>\n");
         
@@ -381,17 +380,14 @@ impl BenchmarkPlotter {
         // Recommendations
         html.push_str("<h2>Architecture Recommendations</h2>\n");
         html.push_str("<div class='metric'>\n");
-        html.push_str("<h3>For Ultra-Low Latency (&lt; 1µs):</h3>\n");
         html.push_str("<ul>\n");
-        html.push_str("<li>Use sync operations with single-threaded HashMap</li>\n");
-        html.push_str("<li>Minimize allocations and string operations</li>\n");
-        html.push_str("<li>Pre-allocate order pools</li>\n");
-        html.push_str("</ul>\n");
-        html.push_str("<h3>For High Throughput (1M+ orders/sec):</h3>\n");
-        html.push_str("<ul>\n");
-        html.push_str("<li>Use concurrent operations with DashMap</li>\n");
-        html.push_str("<li>Implement order batching and periodic cleanup</li>\n");
-        html.push_str("<li>Consider lock-free data structures</li>\n");
+        let recommendations = recommendations(&self.results);
+        if recommendations.is_empty() {
+            html.push_str("<li>Not enough results to compare variants</li>\n");
+        }
+        for recommendation in recommendations {
+            html.push_str(&format!("<li>{}</li>\n", markdown_bold_to_html(&recommendation)));
+        }
         html.push_str("</ul>\n");
         html.push_str("</div>\n");
         
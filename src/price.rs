@@ -0,0 +1,345 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Number of decimal places a [`Price`] can represent.
+pub const PRICE_DECIMALS: u32 = 4;
+
+/// Integer units per whole currency unit.
+pub const PRICE_SCALE: i64 = 10_i64.pow(PRICE_DECIMALS);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceError {
+    InvalidFormat(String),
+    TooPrecise(String),
+    OutOfRange(String),
+    NotFinite(f64),
+    InvalidTickSize(Price),
+    InvalidLotSize,
+    NotOnTick { price: Price, tick_size: Price },
+    NotOnLot { quantity: u64, lot_size: u64 },
+}
+
+impl fmt::Display for PriceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PriceError::InvalidFormat(value) => write!(f, "invalid price: {:?}", value),
+            PriceError::TooPrecise(value) => write!(
+                f,
+                "price {:?} has more than {} decimal places",
+                value, PRICE_DECIMALS
+            ),
+            PriceError::OutOfRange(value) => write!(f, "price {} is out of range", value),
+            PriceError::NotFinite(value) => write!(f, "price {} is not finite", value),
+            PriceError::InvalidTickSize(tick) => write!(f, "tick size {} must be positive", tick),
+            PriceError::InvalidLotSize => write!(f, "lot size must be greater than zero"),
+            PriceError::NotOnTick { price, tick_size } => {
+                write!(f, "price {} is not a multiple of tick size {}", price, tick_size)
+            }
+            PriceError::NotOnLot { quantity, lot_size } => {
+                write!(f, "quantity {} is not a multiple of lot size {}", quantity, lot_size)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PriceError {}
+
+/// Fixed-point price stored as an integer number of `1 / PRICE_SCALE` units,
+/// so equal prices compare equal and can key price levels directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Price(i64);
+
+impl Price {
+    pub const ZERO: Price = Price(0);
+
+    pub const fn from_units(units: i64) -> Self {
+        Price(units)
+    }
+
+    pub const fn units(self) -> i64 {
+        self.0
+    }
+
+    /// Rounds to the nearest representable unit.
+    pub fn from_f64(value: f64) -> Result<Self, PriceError> {
+        if !value.is_finite() {
+            return Err(PriceError::NotFinite(value));
+        }
+        let units = (value * PRICE_SCALE as f64).round();
+        if units < i64::MIN as f64 || units > i64::MAX as f64 {
+            return Err(PriceError::OutOfRange(value.to_string()));
+        }
+        Ok(Price(units as i64))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / PRICE_SCALE as f64
+    }
+
+    /// Builds a price from a whole number of cents.
+    pub const fn from_cents(cents: i64) -> Self {
+        Price(cents * (PRICE_SCALE / 100))
+    }
+
+    pub fn is_multiple_of(self, tick_size: Price) -> bool {
+        tick_size.0 > 0 && self.0 % tick_size.0 == 0
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let units = self.0.unsigned_abs();
+        let whole = units / PRICE_SCALE as u64;
+        let fraction = format!("{:0width$}", units % PRICE_SCALE as u64, width = PRICE_DECIMALS as usize);
+        // Always show cents, drop any further trailing zeros
+        let fraction = fraction.trim_end_matches('0');
+        write!(f, "{}{}.{:0<2}", sign, whole, fraction)
+    }
+}
+
+impl FromStr for Price {
+    type Err = PriceError;
+
+    /// Parses a plain decimal such as `101.25` or `-0.5` without going
+    /// through floating point.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || PriceError::InvalidFormat(value.to_string());
+        let trimmed = value.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+        if !whole.chars().all(|c| c.is_ascii_digit()) || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > PRICE_DECIMALS as usize {
+            return Err(PriceError::TooPrecise(value.to_string()));
+        }
+
+        let out_of_range = || PriceError::OutOfRange(value.to_string());
+        let whole_units = if whole.is_empty() {
+            0
+        } else {
+            whole.parse::<i64>().map_err(|_| out_of_range())?
+        };
+        let fraction_units = if fraction.is_empty() {
+            0
+        } else {
+            let padded = format!("{:0<width$}", fraction, width = PRICE_DECIMALS as usize);
+            padded.parse::<i64>().map_err(|_| invalid())?
+        };
+
+        let units = whole_units
+            .checked_mul(PRICE_SCALE)
+            .and_then(|units| units.checked_add(fraction_units))
+            .ok_or_else(out_of_range)?;
+        Ok(Price(if negative { -units } else { units }))
+    }
+}
+
+/// Trading parameters for a single symbol. Deserializing goes through
+/// [`SymbolSpec::new`], so a spec file cannot hold a zero tick or lot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawSymbolSpec")]
+pub struct SymbolSpec {
+    pub tick_size: Price,
+    pub lot_size: u64,
+}
+
+#[derive(Deserialize)]
+struct RawSymbolSpec {
+    tick_size: Price,
+    lot_size: u64,
+}
+
+impl TryFrom<RawSymbolSpec> for SymbolSpec {
+    type Error = PriceError;
+
+    fn try_from(raw: RawSymbolSpec) -> Result<Self, Self::Error> {
+        SymbolSpec::new(raw.tick_size, raw.lot_size)
+    }
+}
+
+impl Default for SymbolSpec {
+    /// One cent ticks and single-share lots.
+    fn default() -> Self {
+        Self {
+            tick_size: Price::from_cents(1),
+            lot_size: 1,
+        }
+    }
+}
+
+impl SymbolSpec {
+    pub fn new(tick_size: Price, lot_size: u64) -> Result<Self, PriceError> {
+        if tick_size.units() <= 0 {
+            return Err(PriceError::InvalidTickSize(tick_size));
+        }
+        if lot_size == 0 {
+            return Err(PriceError::InvalidLotSize);
+        }
+        Ok(Self { tick_size, lot_size })
+    }
+
+    pub fn validate_price(&self, price: Price) -> Result<Price, PriceError> {
+        if price.is_multiple_of(self.tick_size) {
+            Ok(price)
+        } else {
+            Err(PriceError::NotOnTick {
+                price,
+                tick_size: self.tick_size,
+            })
+        }
+    }
+
+    pub fn validate_quantity(&self, quantity: u64) -> Result<u64, PriceError> {
+        if quantity.is_multiple_of(self.lot_size) {
+            Ok(quantity)
+        } else {
+            Err(PriceError::NotOnLot {
+                quantity,
+                lot_size: self.lot_size,
+            })
+        }
+    }
+
+    /// Converts a floating-point price, failing if it is not on a tick.
+    pub fn price_from_f64(&self, value: f64) -> Result<Price, PriceError> {
+        self.validate_price(Price::from_f64(value)?)
+    }
+
+    /// Parses a decimal price string, failing if it is not on a tick.
+    pub fn parse_price(&self, value: &str) -> Result<Price, PriceError> {
+        self.validate_price(value.parse()?)
+    }
+
+    /// Snaps a floating-point price to the nearest tick.
+    pub fn round_to_tick(&self, value: f64) -> Result<Price, PriceError> {
+        let tick = self.tick_size.units();
+        let units = Price::from_f64(value)?.units();
+        let rounded = ((units as f64 / tick as f64).round() as i64)
+            .checked_mul(tick)
+            .ok_or_else(|| PriceError::OutOfRange(value.to_string()))?;
+        Ok(Price::from_units(rounded))
+    }
+}
+
+/// Per-symbol [`SymbolSpec`]s with a fallback for unlisted symbols.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolSpecs {
+    pub default: SymbolSpec,
+    pub symbols: HashMap<String, SymbolSpec>,
+}
+
+impl SymbolSpecs {
+    pub fn new(default: SymbolSpec) -> Self {
+        Self {
+            default,
+            symbols: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, symbol: String, spec: SymbolSpec) {
+        self.symbols.insert(symbol, spec);
+    }
+
+    pub fn get(&self, symbol: &str) -> &SymbolSpec {
+        self.symbols.get(symbol).unwrap_or(&self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(value: &str) -> Price {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_decimals_exactly() {
+        assert_eq!(price("101.25"), Price::from_units(1_012_500));
+        assert_eq!(price("-0.5"), Price::from_units(-5_000));
+        assert_eq!(price(".5"), Price::from_units(5_000));
+        assert_eq!(price("7"), Price::from_units(70_000));
+        assert_eq!(price(" 1.10000 "), Price::from_units(11_000));
+        assert_eq!(price("0.1").units() + price("0.2").units(), price("0.3").units());
+    }
+
+    #[test]
+    fn rejects_malformed_and_too_precise_prices() {
+        assert!(matches!("".parse::<Price>(), Err(PriceError::InvalidFormat(_))));
+        assert!(matches!(".".parse::<Price>(), Err(PriceError::InvalidFormat(_))));
+        assert!(matches!("1.2.3".parse::<Price>(), Err(PriceError::InvalidFormat(_))));
+        assert!(matches!("1e3".parse::<Price>(), Err(PriceError::InvalidFormat(_))));
+        assert!(matches!("0.00001".parse::<Price>(), Err(PriceError::TooPrecise(_))));
+        assert!(matches!("99999999999999999".parse::<Price>(), Err(PriceError::OutOfRange(_))));
+    }
+
+    #[test]
+    fn converts_from_f64_to_the_nearest_unit() {
+        assert_eq!(Price::from_f64(0.1 + 0.2).unwrap(), price("0.3"));
+        assert_eq!(Price::from_f64(1.23456).unwrap(), price("1.2346"));
+        assert_eq!(Price::from_f64(-1.23454).unwrap(), price("-1.2345"));
+        assert!(matches!(Price::from_f64(f64::INFINITY), Err(PriceError::NotFinite(_))));
+        assert_eq!(price("101.25").to_f64(), 101.25);
+    }
+
+    #[test]
+    fn displays_at_least_cents() {
+        assert_eq!(price("101").to_string(), "101.00");
+        assert_eq!(price("101.5").to_string(), "101.50");
+        assert_eq!(price("0.0125").to_string(), "0.0125");
+        assert_eq!(price("-0.5").to_string(), "-0.50");
+    }
+
+    #[test]
+    fn rounds_and_validates_against_the_tick() {
+        let spec = SymbolSpec::new(price("0.05"), 100).unwrap();
+
+        assert_eq!(spec.round_to_tick(100.024).unwrap(), price("100.00"));
+        assert_eq!(spec.round_to_tick(100.026).unwrap(), price("100.05"));
+        assert_eq!(spec.round_to_tick(-0.026).unwrap(), price("-0.05"));
+        assert_eq!(spec.parse_price("100.05").unwrap(), price("100.05"));
+        assert!(matches!(spec.parse_price("100.03"), Err(PriceError::NotOnTick { .. })));
+        assert!(matches!(spec.price_from_f64(100.07), Err(PriceError::NotOnTick { .. })));
+        assert_eq!(spec.validate_quantity(300), Ok(300));
+        assert_eq!(spec.validate_quantity(150), Err(PriceError::NotOnLot { quantity: 150, lot_size: 100 }));
+    }
+
+    #[test]
+    fn rejects_invalid_specs() {
+        assert_eq!(SymbolSpec::new(Price::ZERO, 1), Err(PriceError::InvalidTickSize(Price::ZERO)));
+        assert_eq!(SymbolSpec::new(price("0.01"), 0), Err(PriceError::InvalidLotSize));
+    }
+
+    #[test]
+    fn deserializing_a_spec_validates_it() {
+        let spec: SymbolSpec = serde_json::from_str(r#"{ "tick_size": 500, "lot_size": 10 }"#).unwrap();
+        assert_eq!(spec, SymbolSpec::new(price("0.05"), 10).unwrap());
+
+        assert!(serde_json::from_str::<SymbolSpec>(r#"{ "tick_size": 0, "lot_size": 10 }"#).is_err());
+        assert!(serde_json::from_str::<SymbolSpec>(r#"{ "tick_size": 500, "lot_size": 0 }"#).is_err());
+        assert!(serde_json::from_str::<SymbolSpecs>(r#"{ "default": { "tick_size": -1, "lot_size": 1 }, "symbols": {} }"#).is_err());
+    }
+
+    #[test]
+    fn rounding_far_outside_the_range_fails() {
+        let spec = SymbolSpec::new(Price::from_units(5_000_000_000_000_000_000), 1).unwrap();
+
+        assert_eq!(spec.round_to_tick(7.0e14).unwrap(), Price::from_units(5_000_000_000_000_000_000));
+        assert!(matches!(spec.round_to_tick(8.0e14), Err(PriceError::OutOfRange(_))));
+        assert_eq!(Price::from_cents(10_025), price("100.25"));
+    }
+}
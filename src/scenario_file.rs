@@ -0,0 +1,715 @@
+use crate::interner::{KeyStrategy, KeyStrategySelection};
+use crate::scenarios::{Scenario, TaskStrategy};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::runtime::{Builder, Runtime};
+
+/// Scenario file used when `HFT_SCENARIO_FILE` is not set, relative to the
+/// crate root where `cargo bench` runs.
+pub const DEFAULT_SCENARIO_FILE: &str = "scenarios.json";
+
+/// Environment variable pointing the benchmarks at another scenario file.
+pub const SCENARIO_FILE_ENV_VAR: &str = "HFT_SCENARIO_FILE";
+
+/// Separates a benchmark function name from its runtime label, as in
+/// `async@mt4-nolifo`.
+pub const RUNTIME_LABEL_SEPARATOR: char = '@';
+
+/// Benchmark matrix shared by the Criterion bench and the scenario runner.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFile {
+    pub scenarios: Vec<ScenarioSpec>,
+}
+
+/// One Criterion group: a workload, the execution strategies it is run with
+/// and the settings to run them under.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioSpec {
+    /// Criterion group name, the first part of every benchmark id.
+    pub name: String,
+    pub workload: WorkloadSpec,
+    pub execution: Vec<Scenario>,
+    /// Runtimes every `tokio` execution is benchmarked on; the sync
+    /// executions run once regardless.
+    #[serde(default = "default_runtimes")]
+    pub runtimes: Vec<RuntimeSettings>,
+    /// How every `tokio` execution drives its futures; `join_all` unless
+    /// given. Only the order operation, HFT simulation and trillion-scale
+    /// workloads have spawning variants.
+    #[serde(default = "default_tasks")]
+    pub tasks: Vec<TaskStrategy>,
+    /// `strings`, `interned` or `all` (the default) for the workloads with
+    /// interned variants; `HFT_KEY_STRATEGY` overrides it for a run.
+    #[serde(default)]
+    pub key_strategy: KeyStrategySelection,
+    #[serde(default)]
+    pub criterion: CriterionSettings,
+}
+
+/// Workload type and its parameters, tagged by `type`. Lists are matrix
+/// axes; `symbols` and `clients` are the number of distinct symbols and
+/// client ids the generated orders cycle through.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum WorkloadSpec {
+    /// Insert, read and fill every order of a batch.
+    OrderOperations {
+        sizes: Vec<usize>,
+        symbols: usize,
+        clients: usize,
+    },
+    /// Merge per-exchange order maps into one map.
+    OrderFlattening {
+        exchanges: Vec<usize>,
+        orders_per_exchange: Vec<usize>,
+        symbols: usize,
+        clients: usize,
+    },
+    /// Submit, modify and cancel against limit order books.
+    OrderBook {
+        sizes: Vec<usize>,
+        prices: PriceLevels,
+        symbols: usize,
+        clients: usize,
+    },
+    /// Park stop orders on one symbol and replay `trades` trade prices.
+    StopTriggers {
+        sizes: Vec<usize>,
+        trades: usize,
+        clients: usize,
+    },
+    /// Populate `orders` orders, then apply `updates` random fills.
+    HftSimulation {
+        orders: usize,
+        updates: usize,
+        symbols: usize,
+        clients: usize,
+    },
+    /// Fixed-rate load cycling through `orders` orders. The rate falls back
+    /// to `HFT_OPEN_LOOP_RATE` when not given.
+    OpenLoop {
+        orders: usize,
+        #[serde(default)]
+        rate: Option<u64>,
+        symbols: usize,
+        clients: usize,
+    },
+    /// `(batch_size, batches)` runs of orders generated on the fly.
+    TrillionScale {
+        batches: Vec<(usize, usize)>,
+        symbols: usize,
+        clients: usize,
+    },
+}
+
+/// Order book price level keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceLevels {
+    Float,
+    Ticks,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeFlavor {
+    CurrentThread,
+    #[default]
+    MultiThread,
+}
+
+/// Tokio runtime the async executions run on. The default is what
+/// `Runtime::new()` builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeSettings {
+    #[serde(default)]
+    pub flavor: RuntimeFlavor,
+    /// Multi-thread runtime workers; one per core (or `TOKIO_WORKER_THREADS`)
+    /// when not given.
+    #[serde(default)]
+    pub worker_threads: Option<usize>,
+    /// Multi-thread LIFO slot. Disabling it needs `--cfg tokio_unstable`.
+    #[serde(default = "default_lifo_slot")]
+    pub lifo_slot: bool,
+    #[serde(default)]
+    pub event_interval: Option<u32>,
+    #[serde(default)]
+    pub global_queue_interval: Option<u32>,
+}
+
+/// Criterion group overrides, Criterion's defaults when not given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CriterionSettings {
+    #[serde(default)]
+    pub sample_size: Option<usize>,
+    #[serde(default)]
+    pub warm_up_secs: Option<f64>,
+    #[serde(default)]
+    pub measurement_secs: Option<f64>,
+}
+
+impl ScenarioFile {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let json = fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        let file: ScenarioFile = serde_json::from_str(&json).map_err(|err| format!("{}: {}", path.display(), err))?;
+        file.validate().map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(file)
+    }
+
+    /// Loads the file named by `HFT_SCENARIO_FILE`, or [`DEFAULT_SCENARIO_FILE`].
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load(&path_from_env())
+    }
+
+    /// The `scenarios.json` the crate ships with, for reading results of the
+    /// built-in matrix when no scenario file is at hand.
+    pub fn builtin() -> Self {
+        serde_json::from_str(include_str!("../scenarios.json")).expect("built-in scenarios.json is valid")
+    }
+
+    pub fn spec(&self, name: &str) -> Option<&ScenarioSpec> {
+        self.scenarios.iter().find(|spec| spec.name == name)
+    }
+
+    /// Checks what the file format cannot express: unique group names,
+    /// executions the workload supports and non-empty, non-zero parameters.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.scenarios.is_empty() {
+            return Err("no scenarios defined".to_string());
+        }
+        let mut names = HashSet::new();
+        for spec in &self.scenarios {
+            if spec.name.is_empty() || spec.name.contains('/') {
+                return Err(format!("scenario name '{}' must be non-empty and must not contain '/'", spec.name));
+            }
+            if !names.insert(spec.name.as_str()) {
+                return Err(format!("scenario '{}' is defined more than once", spec.name));
+            }
+            spec.validate().map_err(|err| format!("scenario '{}': {}", spec.name, err))?;
+        }
+        Ok(())
+    }
+}
+
+impl ScenarioSpec {
+    /// The per-group checks of [`ScenarioFile::validate`], for specs built
+    /// outside a scenario file.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.execution.is_empty() {
+            return Err("no execution strategies listed".to_string());
+        }
+        for &execution in &self.execution {
+            if self.workload.function_name(execution).is_none() {
+                let supported: Vec<String> = Scenario::ALL
+                    .iter()
+                    .filter(|&&scenario| self.workload.function_name(scenario).is_some())
+                    .map(|scenario| scenario.to_string())
+                    .collect();
+                return Err(format!(
+                    "{} workloads do not support {} execution (supported: {})",
+                    self.workload.kind(),
+                    execution,
+                    supported.join(", ")
+                ));
+            }
+        }
+        self.workload.validate()?;
+        if self.tasks.is_empty() {
+            return Err("tasks must list at least one task strategy".to_string());
+        }
+        if !self.workload.supports_tasks() && self.tasks != default_tasks() {
+            return Err(format!("{} workloads only run with join_all tasks", self.workload.kind()));
+        }
+        if self.tasks.iter().enumerate().any(|(i, tasks)| self.tasks[..i].contains(tasks)) {
+            return Err("tasks lists a task strategy more than once".to_string());
+        }
+        if !self.workload.supports_interned_keys() && self.key_strategy != KeyStrategySelection::All {
+            return Err(format!("{} workloads only run with string keys", self.workload.kind()));
+        }
+        if self.runtimes.is_empty() {
+            return Err("runtimes must list at least one runtime".to_string());
+        }
+        let mut labels = HashSet::new();
+        for runtime in &self.runtimes {
+            runtime.validate()?;
+            if !labels.insert(runtime.label()) {
+                return Err(format!("runtime '{}' is listed more than once", runtime.describe()));
+            }
+        }
+        self.criterion.validate()
+    }
+
+    /// Benchmark function name for `execution`, if this scenario runs it.
+    pub fn function(&self, execution: Scenario) -> Option<&'static str> {
+        if self.execution.contains(&execution) {
+            self.workload.function_name(execution)
+        } else {
+            None
+        }
+    }
+
+    /// Key strategies to benchmark this scenario with. Fails when
+    /// `HFT_KEY_STRATEGY` holds an unknown value.
+    pub fn key_strategies(&self) -> Result<Vec<KeyStrategy>, String> {
+        if self.workload.supports_interned_keys() {
+            KeyStrategy::selected(self.key_strategy)
+        } else {
+            Ok(vec![KeyStrategy::Strings])
+        }
+    }
+
+    /// Listed executions with their benchmark function names, in file order.
+    pub fn functions(&self) -> impl Iterator<Item = (Scenario, &'static str)> + '_ {
+        self.execution
+            .iter()
+            .filter_map(|&execution| self.workload.function_name(execution).map(|function| (execution, function)))
+    }
+}
+
+impl WorkloadSpec {
+    /// `type` value of the workload in a scenario file.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            WorkloadSpec::OrderOperations { .. } => "order_operations",
+            WorkloadSpec::OrderFlattening { .. } => "order_flattening",
+            WorkloadSpec::OrderBook { .. } => "order_book",
+            WorkloadSpec::StopTriggers { .. } => "stop_triggers",
+            WorkloadSpec::HftSimulation { .. } => "hft_simulation",
+            WorkloadSpec::OpenLoop { .. } => "open_loop",
+            WorkloadSpec::TrillionScale { .. } => "trillion_scale",
+        }
+    }
+
+    /// Whether the async execution can spawn its futures as tasks instead
+    /// of joining them on one task.
+    pub fn supports_tasks(&self) -> bool {
+        matches!(
+            self,
+            WorkloadSpec::OrderOperations { .. } | WorkloadSpec::HftSimulation { .. } | WorkloadSpec::TrillionScale { .. }
+        )
+    }
+
+    /// Whether the workload has variants keyed by numeric order ids and
+    /// interned symbols and clients.
+    pub fn supports_interned_keys(&self) -> bool {
+        matches!(self, WorkloadSpec::OrderOperations { .. } | WorkloadSpec::HftSimulation { .. })
+    }
+
+    /// Execution a benchmark function name of this workload belongs to,
+    /// whether or not the scenario lists it.
+    pub fn execution_of(&self, function: &str) -> Option<Scenario> {
+        Scenario::ALL
+            .into_iter()
+            .find(|&execution| self.function_name(execution) == Some(function))
+    }
+
+    /// Operation type reported for `execution` of this workload: the
+    /// workload family followed by the variant, e.g. "Tick Book Concurrent".
+    /// The labels match the ones reports have always used.
+    pub fn operation_type(&self, execution: Scenario) -> String {
+        let standard = match execution {
+            Scenario::Sync => "Sync",
+            Scenario::DashMap => "Concurrent",
+            Scenario::Tokio => "Async",
+        };
+        // The sync_ simulations drive a DashMap from a single thread
+        let single_threaded = match execution {
+            Scenario::Sync | Scenario::DashMap => "Sync",
+            Scenario::Tokio => "Async",
+        };
+        let (family, variant) = match self {
+            WorkloadSpec::OrderOperations { .. } => return execution.operation_type().to_string(),
+            WorkloadSpec::OrderFlattening { .. } => ("Flatten", standard),
+            WorkloadSpec::OrderBook { prices: PriceLevels::Float, .. } => ("Book", standard),
+            WorkloadSpec::OrderBook { prices: PriceLevels::Ticks, .. } => ("Tick Book", standard),
+            WorkloadSpec::StopTriggers { .. } => ("Stop", standard),
+            WorkloadSpec::HftSimulation { .. } => ("HFT Simulation", single_threaded),
+            WorkloadSpec::TrillionScale { .. } => ("Trillion Scale", single_threaded),
+            WorkloadSpec::OpenLoop { .. } => {
+                let variant = match execution {
+                    Scenario::Sync => "Sync",
+                    Scenario::DashMap => "DashMap",
+                    Scenario::Tokio => "Tokio",
+                };
+                ("Open Loop", variant)
+            }
+        };
+        format!("{} {}", family, variant)
+    }
+
+    /// Benchmark function name of `execution` for this workload, or `None`
+    /// when the workload has no such variant. These are the names the
+    /// benchmarks had before scenario files, such as `single_threaded`.
+    pub fn function_name(&self, execution: Scenario) -> Option<&'static str> {
+        match (self, execution) {
+            (WorkloadSpec::OrderOperations { .. }, _) => Some(execution.benchmark_id().1),
+            (WorkloadSpec::OrderFlattening { .. }, Scenario::Sync) => Some("sync"),
+            (WorkloadSpec::OrderFlattening { .. }, Scenario::Tokio) => Some("async"),
+            (WorkloadSpec::OrderBook { .. }, Scenario::Sync) => Some("sync"),
+            (WorkloadSpec::OrderBook { .. }, Scenario::DashMap) => Some("concurrent"),
+            (WorkloadSpec::OrderBook { .. }, Scenario::Tokio) => Some("async"),
+            (WorkloadSpec::StopTriggers { .. }, Scenario::Sync) => Some("sync_scan"),
+            (WorkloadSpec::StopTriggers { .. }, Scenario::Tokio) => Some("async_notifier"),
+            (WorkloadSpec::HftSimulation { .. }, Scenario::DashMap) => Some("sync_hft_orders"),
+            (WorkloadSpec::HftSimulation { .. }, Scenario::Tokio) => Some("async_hft_orders"),
+            (WorkloadSpec::OpenLoop { .. }, Scenario::Sync) => Some("sync_hashmap"),
+            (WorkloadSpec::OpenLoop { .. }, Scenario::DashMap) => Some("dashmap"),
+            (WorkloadSpec::OpenLoop { .. }, Scenario::Tokio) => Some("tokio"),
+            (WorkloadSpec::TrillionScale { .. }, Scenario::DashMap) => Some("sync_trillion"),
+            (WorkloadSpec::TrillionScale { .. }, Scenario::Tokio) => Some("async_trillion"),
+            _ => None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let axis = |name: &str, values: &[usize]| {
+            if values.is_empty() || values.contains(&0) {
+                Err(format!("{} must list at least one value and no zeros", name))
+            } else {
+                Ok(())
+            }
+        };
+        let count = |name: &str, value: usize| {
+            if value == 0 {
+                Err(format!("{} must be at least 1", name))
+            } else {
+                Ok(())
+            }
+        };
+        match self {
+            WorkloadSpec::OrderOperations { sizes, symbols, clients }
+            | WorkloadSpec::OrderBook { sizes, symbols, clients, .. } => {
+                axis("sizes", sizes)?;
+                count("symbols", *symbols)?;
+                count("clients", *clients)
+            }
+            WorkloadSpec::OrderFlattening { exchanges, orders_per_exchange, symbols, clients } => {
+                axis("exchanges", exchanges)?;
+                axis("orders_per_exchange", orders_per_exchange)?;
+                count("symbols", *symbols)?;
+                count("clients", *clients)
+            }
+            WorkloadSpec::StopTriggers { sizes, trades, clients } => {
+                axis("sizes", sizes)?;
+                count("trades", *trades)?;
+                count("clients", *clients)
+            }
+            WorkloadSpec::HftSimulation { orders, updates, symbols, clients } => {
+                count("orders", *orders)?;
+                count("updates", *updates)?;
+                count("symbols", *symbols)?;
+                count("clients", *clients)
+            }
+            WorkloadSpec::OpenLoop { orders, rate, symbols, clients } => {
+                count("orders", *orders)?;
+                if *rate == Some(0) {
+                    return Err("rate must be at least 1".to_string());
+                }
+                count("symbols", *symbols)?;
+                count("clients", *clients)
+            }
+            WorkloadSpec::TrillionScale { batches, symbols, clients } => {
+                if batches.is_empty() || batches.iter().any(|&(batch_size, count)| batch_size == 0 || count == 0) {
+                    return Err("batches must list at least one [batch_size, batches] pair and no zeros".to_string());
+                }
+                count("symbols", *symbols)?;
+                count("clients", *clients)
+            }
+        }
+    }
+}
+
+impl Default for RuntimeSettings {
+    fn default() -> Self {
+        Self {
+            flavor: RuntimeFlavor::MultiThread,
+            worker_threads: None,
+            lifo_slot: true,
+            event_interval: None,
+            global_queue_interval: None,
+        }
+    }
+}
+
+impl RuntimeSettings {
+    pub fn current_thread() -> Self {
+        Self {
+            flavor: RuntimeFlavor::CurrentThread,
+            ..Self::default()
+        }
+    }
+
+    pub fn multi_thread(worker_threads: usize) -> Self {
+        Self {
+            worker_threads: Some(worker_threads),
+            ..Self::default()
+        }
+    }
+
+    pub fn build(&self) -> std::io::Result<Runtime> {
+        // Tokio panics on zero workers or intervals instead of erroring
+        self.validate()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        let mut builder = match self.flavor {
+            RuntimeFlavor::CurrentThread => Builder::new_current_thread(),
+            RuntimeFlavor::MultiThread => Builder::new_multi_thread(),
+        };
+        builder.enable_all();
+        if let Some(workers) = self.worker_threads {
+            builder.worker_threads(workers);
+        }
+        if let Some(interval) = self.event_interval {
+            builder.event_interval(interval);
+        }
+        if let Some(interval) = self.global_queue_interval {
+            builder.global_queue_interval(interval);
+        }
+        if !self.lifo_slot {
+            #[cfg(tokio_unstable)]
+            builder.disable_lifo_slot();
+            #[cfg(not(tokio_unstable))]
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "disabling the LIFO slot needs RUSTFLAGS=\"--cfg tokio_unstable\"",
+            ));
+        }
+        builder.build()
+    }
+
+    /// Suffix added to benchmark function names: `@current_thread`, `@mt4`,
+    /// `@mt-nolifo-ev31-gq61`, ... The default runtime adds none, leaving
+    /// e.g. `async_order_operations/async/100` as it was.
+    pub fn label(&self) -> String {
+        if *self == Self::default() {
+            return String::new();
+        }
+        let mut parts = vec![match (self.flavor, self.worker_threads) {
+            (RuntimeFlavor::CurrentThread, _) => "current_thread".to_string(),
+            (RuntimeFlavor::MultiThread, Some(workers)) => format!("mt{}", workers),
+            (RuntimeFlavor::MultiThread, None) => "mt".to_string(),
+        }];
+        if !self.lifo_slot {
+            parts.push("nolifo".to_string());
+        }
+        if let Some(interval) = self.event_interval {
+            parts.push(format!("ev{}", interval));
+        }
+        if let Some(interval) = self.global_queue_interval {
+            parts.push(format!("gq{}", interval));
+        }
+        format!("{}{}", RUNTIME_LABEL_SEPARATOR, parts.join("-"))
+    }
+
+    /// Reads a [`RuntimeSettings::label`] back, with or without the leading
+    /// separator. `default` names the default runtime. Labels that could not
+    /// build a runtime, such as `mt0`, are rejected.
+    pub fn parse(label: &str) -> Option<Self> {
+        let label = label.trim();
+        let label = label.strip_prefix(RUNTIME_LABEL_SEPARATOR).unwrap_or(label);
+        let mut parts = label.split('-');
+        let mut settings = match parts.next()? {
+            "default" | "mt" => Self::default(),
+            "current_thread" | "ct" => Self::current_thread(),
+            flavor => Self::multi_thread(flavor.strip_prefix("mt")?.parse().ok()?),
+        };
+        for part in parts {
+            if part == "nolifo" {
+                settings.lifo_slot = false;
+            } else if let Some(interval) = part.strip_prefix("ev") {
+                settings.event_interval = Some(interval.parse().ok()?);
+            } else if let Some(interval) = part.strip_prefix("gq") {
+                settings.global_queue_interval = Some(interval.parse().ok()?);
+            } else {
+                return None;
+            }
+        }
+        settings.validate().ok().map(|_| settings)
+    }
+
+    /// Operation type of a benchmark run on this runtime: `base` for the
+    /// default runtime, otherwise `base` followed by the description.
+    pub fn operation_type(&self, base: &str) -> String {
+        if *self == Self::default() {
+            base.to_string()
+        } else {
+            format!("{} [{}]", base, self.describe())
+        }
+    }
+
+    /// Human-readable form for operation types, e.g.
+    /// "multi_thread, 4 workers, no LIFO slot".
+    pub fn describe(&self) -> String {
+        let mut parts = vec![match self.flavor {
+            RuntimeFlavor::CurrentThread => "current_thread".to_string(),
+            RuntimeFlavor::MultiThread => "multi_thread".to_string(),
+        }];
+        if let Some(workers) = self.worker_threads {
+            parts.push(format!("{} worker{}", workers, if workers == 1 { "" } else { "s" }));
+        }
+        if !self.lifo_slot {
+            parts.push("no LIFO slot".to_string());
+        }
+        if let Some(interval) = self.event_interval {
+            parts.push(format!("event_interval {}", interval));
+        }
+        if let Some(interval) = self.global_queue_interval {
+            parts.push(format!("global_queue_interval {}", interval));
+        }
+        parts.join(", ")
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.flavor == RuntimeFlavor::CurrentThread && (self.worker_threads.is_some() || !self.lifo_slot) {
+            return Err("worker_threads and lifo_slot only apply to multi_thread runtimes".to_string());
+        }
+        if self.worker_threads == Some(0) || self.event_interval == Some(0) || self.global_queue_interval == Some(0) {
+            return Err("runtime worker_threads and intervals must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn default_runtimes() -> Vec<RuntimeSettings> {
+    vec![RuntimeSettings::default()]
+}
+
+fn default_tasks() -> Vec<TaskStrategy> {
+    vec![TaskStrategy::JoinAll]
+}
+
+fn default_lifo_slot() -> bool {
+    true
+}
+
+impl CriterionSettings {
+    pub fn warm_up_time(&self) -> Option<Duration> {
+        self.warm_up_secs.map(Duration::from_secs_f64)
+    }
+
+    pub fn measurement_time(&self) -> Option<Duration> {
+        self.measurement_secs.map(Duration::from_secs_f64)
+    }
+
+    // Criterion panics on fewer than 10 samples or non-positive durations
+    fn validate(&self) -> Result<(), String> {
+        if self.sample_size.is_some_and(|size| size < 10) {
+            return Err("criterion.sample_size must be at least 10".to_string());
+        }
+        let positive = |secs: Option<f64>| secs.is_none_or(|secs| secs.is_finite() && secs > 0.0);
+        if !positive(self.warm_up_secs) || !positive(self.measurement_secs) {
+            return Err("criterion durations must be positive numbers of seconds".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub fn path_from_env() -> PathBuf {
+    std::env::var(SCENARIO_FILE_ENV_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_SCENARIO_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Result<ScenarioFile, String> {
+        let file: ScenarioFile = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        file.validate()?;
+        Ok(file)
+    }
+
+    // One order_operations group with `extra` fields appended
+    fn order_operations(sizes: &str, execution: &str, extra: &str) -> String {
+        format!(
+            r#"{{ "scenarios": [{{ "name": "orders", "workload": {{ "type": "order_operations", "sizes": {}, "symbols": 10, "clients": 5 }}, "execution": {}{} }}] }}"#,
+            sizes, execution, extra
+        )
+    }
+
+    #[test]
+    fn shipped_scenario_file_is_valid() {
+        ScenarioFile::builtin().validate().unwrap();
+    }
+
+    #[test]
+    fn accepts_a_minimal_group() {
+        let file = parse(&order_operations("[100]", r#"["sync", "tokio"]"#, "")).unwrap();
+        let spec = &file.scenarios[0];
+
+        assert_eq!(spec.runtimes, vec![RuntimeSettings::default()]);
+        assert_eq!(spec.tasks, vec![TaskStrategy::JoinAll]);
+        assert_eq!(spec.key_strategy, KeyStrategySelection::All);
+    }
+
+    #[test]
+    fn rejects_unknown_executions_and_fields() {
+        assert!(parse(&order_operations("[100]", r#"["threads"]"#, "")).is_err());
+        assert!(parse(&order_operations("[100]", r#"["sync"]"#, r#", "repeat": 3"#)).is_err());
+        let err = parse(&order_operations("[100]", "[]", "")).unwrap_err();
+        assert!(err.contains("no execution strategies"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_empty_or_zero_sizes() {
+        for sizes in ["[]", "[100, 0]"] {
+            let err = parse(&order_operations(sizes, r#"["sync"]"#, "")).unwrap_err();
+            assert!(err.contains("sizes"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_task_strategies() {
+        for tasks in [r#"["chunked:0"]"#, r#"["chunked:x"]"#, r#"["threads"]"#] {
+            let extra = format!(r#", "tasks": {}"#, tasks);
+            assert!(parse(&order_operations("[100]", r#"["tokio"]"#, &extra)).is_err(), "{}", tasks);
+        }
+        let err = parse(&order_operations("[100]", r#"["tokio"]"#, r#", "tasks": ["spawn", "spawn"]"#)).unwrap_err();
+        assert!(err.contains("more than once"), "{}", err);
+        let err = parse(&order_operations("[100]", r#"["tokio"]"#, r#", "tasks": []"#)).unwrap_err();
+        assert!(err.contains("at least one task strategy"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_duplicate_and_unsupported_groups() {
+        let group = r#"{ "name": "orders", "workload": { "type": "order_operations", "sizes": [100], "symbols": 10, "clients": 5 }, "execution": ["sync"] }"#;
+        let err = parse(&format!(r#"{{ "scenarios": [{}, {}] }}"#, group, group)).unwrap_err();
+        assert!(err.contains("more than once"), "{}", err);
+
+        let stops = r#"{ "scenarios": [{ "name": "stops", "workload": { "type": "stop_triggers", "sizes": [100], "trades": 10, "clients": 5 }, "execution": ["sync"], "tasks": ["spawn"] }] }"#;
+        let err = parse(stops).unwrap_err();
+        assert!(err.contains("only run with join_all"), "{}", err);
+
+        assert_eq!(parse(r#"{ "scenarios": [] }"#).unwrap_err(), "no scenarios defined");
+    }
+
+    #[test]
+    fn parses_runtime_labels_back() {
+        for settings in [
+            RuntimeSettings::default(),
+            RuntimeSettings::current_thread(),
+            RuntimeSettings::multi_thread(4),
+            RuntimeSettings { event_interval: Some(31), global_queue_interval: Some(61), ..RuntimeSettings::multi_thread(2) },
+        ] {
+            let label = settings.label();
+            let label = if label.is_empty() { "default".to_string() } else { label };
+            assert_eq!(RuntimeSettings::parse(&label), Some(settings));
+        }
+    }
+
+    #[test]
+    fn rejects_zero_runtime_counts() {
+        for label in ["mt0", "mt-ev0", "mt4-gq0", "current_thread-nolifo"] {
+            assert_eq!(RuntimeSettings::parse(label), None, "{}", label);
+        }
+        let zero_workers = RuntimeSettings::multi_thread(0);
+        assert_eq!(zero_workers.build().unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,591 @@
+use crate::orders::Order;
+use crate::scenario_file::RuntimeSettings;
+use crate::simple_plotter::BenchmarkResult;
+use crate::workload::OrderWorkload;
+use dashmap::DashMap;
+use futures::future::join_all;
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::runtime::Runtime;
+use tokio::task::JoinSet;
+
+/// Two-sided z value of a 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Order workload variant timed by the scenario runner and the benchmarks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scenario {
+    /// Single-threaded `HashMap`.
+    Sync,
+    /// `DashMap` driven from one thread.
+    DashMap,
+    /// `DashMap` driven by one Tokio future per operation.
+    Tokio,
+}
+
+impl Scenario {
+    pub const ALL: [Scenario; 3] = [Scenario::Sync, Scenario::DashMap, Scenario::Tokio];
+
+    /// Criterion group and function the scenario is benchmarked under, so
+    /// runner results line up with `plot_results` output.
+    pub fn benchmark_id(&self) -> (&'static str, &'static str) {
+        match self {
+            Scenario::Sync => ("sync_order_operations", "single_threaded"),
+            Scenario::DashMap => ("sync_order_operations", "concurrent"),
+            Scenario::Tokio => ("async_order_operations", "async"),
+        }
+    }
+
+    pub fn operation_type(&self) -> &'static str {
+        match self {
+            Scenario::Sync => "Sync Single",
+            Scenario::DashMap => "Sync Concurrent",
+            Scenario::Tokio => "Async",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Scenario> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "sync" | "hashmap" | "single_threaded" => Some(Scenario::Sync),
+            "dashmap" | "concurrent" => Some(Scenario::DashMap),
+            "tokio" | "async" => Some(Scenario::Tokio),
+            _ => None,
+        }
+    }
+
+    /// Runs the scenario once over `workload`.
+    pub fn execute(&self, workload: &OrderWorkload, runtime: &Runtime) {
+        self.execute_with(TaskStrategy::JoinAll, workload, runtime)
+    }
+
+    /// Runs the scenario once, driving the Tokio futures with `tasks`. The
+    /// sync scenarios ignore `tasks` and `runtime`.
+    pub fn execute_with(&self, tasks: TaskStrategy, workload: &OrderWorkload, runtime: &Runtime) {
+        match (self, tasks) {
+            (Scenario::Sync, _) => sync_order_operations(workload),
+            (Scenario::DashMap, _) => sync_concurrent_order_operations(workload),
+            (Scenario::Tokio, TaskStrategy::JoinAll) => runtime.block_on(async_order_operations(workload)),
+            (Scenario::Tokio, tasks) => runtime.block_on(spawned_order_operations(workload, tasks)),
+        }
+    }
+}
+
+impl fmt::Display for Scenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Scenario::Sync => "sync",
+            Scenario::DashMap => "dashmap",
+            Scenario::Tokio => "tokio",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How the async variants drive their per-order futures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TaskStrategy {
+    /// `join_all` on the calling task: concurrency without parallelism.
+    JoinAll,
+    /// One `tokio::spawn`ed task per order, awaited through its handle.
+    Spawn,
+    /// One task per order in a `JoinSet`.
+    JoinSet,
+    /// One spawned task per chunk of this many orders.
+    Chunked(usize),
+    /// `FuturesUnordered` on the calling task, like `join_all` without
+    /// keeping results in order.
+    FuturesUnordered,
+}
+
+impl TaskStrategy {
+    /// Suffix appended to benchmark function names, such as `_spawn` or
+    /// `_chunked64`. `join_all` was the only strategy before and adds none.
+    pub fn suffix(&self) -> String {
+        match self {
+            TaskStrategy::JoinAll => String::new(),
+            TaskStrategy::Spawn => "_spawn".to_string(),
+            TaskStrategy::JoinSet => "_join_set".to_string(),
+            TaskStrategy::Chunked(size) => format!("_chunked{}", size),
+            TaskStrategy::FuturesUnordered => "_futures_unordered".to_string(),
+        }
+    }
+
+    /// Splits a benchmark function name into its base name and strategy.
+    pub fn strip_suffix(function: &str) -> (&str, TaskStrategy) {
+        for tasks in [TaskStrategy::Spawn, TaskStrategy::JoinSet, TaskStrategy::FuturesUnordered] {
+            if let Some(base) = function.strip_suffix(tasks.suffix().as_str()) {
+                return (base, tasks);
+            }
+        }
+        if let Some((base, size)) = function.rsplit_once("_chunked") {
+            if let Ok(size) = size.parse() {
+                return (base, TaskStrategy::Chunked(size));
+            }
+        }
+        (function, TaskStrategy::JoinAll)
+    }
+
+    /// Words added to the operation type, empty for `join_all`.
+    pub fn operation_name(&self) -> String {
+        match self {
+            TaskStrategy::JoinAll => String::new(),
+            TaskStrategy::Spawn => "Spawn".to_string(),
+            TaskStrategy::JoinSet => "JoinSet".to_string(),
+            TaskStrategy::Chunked(size) => format!("Chunked {}", size),
+            TaskStrategy::FuturesUnordered => "FuturesUnordered".to_string(),
+        }
+    }
+
+    /// Whether the futures run as their own tasks, and so across workers.
+    pub fn spawns_tasks(&self) -> bool {
+        matches!(self, TaskStrategy::Spawn | TaskStrategy::JoinSet | TaskStrategy::Chunked(_))
+    }
+
+    /// `join_all`, `spawn`, `join_set`, `futures_unordered` or `chunked:<size>`.
+    pub fn parse(value: &str) -> Option<TaskStrategy> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "join_all" => Some(TaskStrategy::JoinAll),
+            "spawn" => Some(TaskStrategy::Spawn),
+            "join_set" => Some(TaskStrategy::JoinSet),
+            "futures_unordered" => Some(TaskStrategy::FuturesUnordered),
+            other => other
+                .strip_prefix("chunked:")
+                .and_then(|size| size.parse().ok())
+                .filter(|&size| size > 0)
+                .map(TaskStrategy::Chunked),
+        }
+    }
+}
+
+impl fmt::Display for TaskStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskStrategy::JoinAll => write!(f, "join_all"),
+            TaskStrategy::Spawn => write!(f, "spawn"),
+            TaskStrategy::JoinSet => write!(f, "join_set"),
+            TaskStrategy::Chunked(size) => write!(f, "chunked:{}", size),
+            TaskStrategy::FuturesUnordered => write!(f, "futures_unordered"),
+        }
+    }
+}
+
+impl TryFrom<String> for TaskStrategy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        TaskStrategy::parse(&value).ok_or_else(|| {
+            format!("unknown task strategy '{}' (expected join_all, spawn, join_set, futures_unordered or chunked:<size>)", value)
+        })
+    }
+}
+
+impl From<TaskStrategy> for String {
+    fn from(tasks: TaskStrategy) -> Self {
+        tasks.to_string()
+    }
+}
+
+/// Drives `futures` to completion with `tasks`. Panics if a spawned task
+/// panicked, as awaiting the future directly would.
+pub async fn run_tasks<F>(tasks: TaskStrategy, futures: Vec<F>)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    match tasks {
+        TaskStrategy::JoinAll => {
+            join_all(futures).await;
+        }
+        TaskStrategy::Spawn => {
+            let handles: Vec<_> = futures.into_iter().map(tokio::spawn).collect();
+            for handle in handles {
+                handle.await.expect("order task panicked");
+            }
+        }
+        TaskStrategy::JoinSet => {
+            let mut set: JoinSet<()> = futures.into_iter().collect();
+            while let Some(result) = set.join_next().await {
+                result.expect("order task panicked");
+            }
+        }
+        TaskStrategy::Chunked(size) => {
+            let mut futures = futures.into_iter().peekable();
+            let mut handles = Vec::new();
+            while futures.peek().is_some() {
+                let chunk: Vec<F> = futures.by_ref().take(size).collect();
+                handles.push(tokio::spawn(async move {
+                    for future in chunk {
+                        future.await;
+                    }
+                }));
+            }
+            for handle in handles {
+                handle.await.expect("order task panicked");
+            }
+        }
+        TaskStrategy::FuturesUnordered => {
+            let mut pending: FuturesUnordered<F> = futures.into_iter().collect();
+            while pending.next().await.is_some() {}
+        }
+    }
+}
+
+/// Inserts order `i` of a workload under its `ORD_{i}` key. Shared by the
+/// `DashMap` scenarios and the latency benchmarks so both time the same work.
+pub fn insert_order(order_map: &DashMap<String, Order>, i: usize, order: Order) {
+    let order_id = format!("ORD_{}", i);
+    order_map.insert(order_id, order);
+}
+
+/// Remaining quantity of order `i`, if it was inserted.
+pub fn query_order(order_map: &DashMap<String, Order>, i: usize) -> Option<u64> {
+    let order_id = format!("ORD_{}", i);
+    order_map.get(&order_id).map(|order| order.remaining_quantity)
+}
+
+/// Applies `fill_qty` to order `i` unless it is already filled.
+pub fn fill_order(order_map: &DashMap<String, Order>, i: usize, fill_qty: u64) {
+    let order_id = format!("ORD_{}", i);
+    if let Some(mut order) = order_map.get_mut(&order_id) {
+        if order.remaining_quantity > 0 {
+            order.update_fill(fill_qty);
+        }
+    }
+}
+
+/// Inserts, reads and fills every order in a single-threaded `HashMap`.
+pub fn sync_order_operations(workload: &OrderWorkload) {
+    let mut order_map: HashMap<String, Order> = HashMap::new();
+
+    // Write operations - Create orders
+    for (i, order) in workload.orders.iter().enumerate() {
+        let order_id = format!("ORD_{}", i);
+        order_map.insert(order_id, order.clone());
+    }
+
+    // Read operations - Query orders
+    for i in 0..workload.len() {
+        let order_id = format!("ORD_{}", i);
+        black_box(order_map.get(&order_id));
+    }
+
+    // Update operations - Fill orders
+    for (i, &fill_qty) in workload.fills.iter().enumerate() {
+        let order_id = format!("ORD_{}", i);
+        if let Some(order) = order_map.get_mut(&order_id) {
+            if order.remaining_quantity > 0 {
+                order.update_fill(fill_qty);
+            }
+        }
+    }
+}
+
+/// Same operations as [`sync_order_operations`] against a `DashMap`.
+pub fn sync_concurrent_order_operations(workload: &OrderWorkload) {
+    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
+
+    // Write operations - Create orders
+    for (i, order) in workload.orders.iter().enumerate() {
+        insert_order(&order_map, i, order.clone());
+    }
+
+    // Read operations - Query orders
+    for i in 0..workload.len() {
+        black_box(query_order(&order_map, i));
+    }
+
+    // Update operations - Fill orders
+    for (i, &fill_qty) in workload.fills.iter().enumerate() {
+        fill_order(&order_map, i, fill_qty);
+    }
+}
+
+/// Same operations as [`sync_order_operations`], one future per operation
+/// joined with `join_all` for each phase.
+pub async fn async_order_operations(workload: &OrderWorkload) {
+    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
+
+    // Write operations - Create orders
+    let write_tasks: Vec<_> = workload
+        .orders
+        .iter()
+        .enumerate()
+        .map(|(i, order)| {
+            let order_map = order_map.clone();
+            async move {
+                insert_order(&order_map, i, order.clone());
+            }
+        })
+        .collect();
+
+    join_all(write_tasks).await;
+
+    // Read operations - Query orders
+    let read_tasks: Vec<_> = (0..workload.len())
+        .map(|i| {
+            let order_map = order_map.clone();
+            async move {
+                black_box(query_order(&order_map, i));
+            }
+        })
+        .collect();
+
+    join_all(read_tasks).await;
+
+    // Update operations - Fill orders
+    let update_tasks: Vec<_> = workload
+        .fills
+        .iter()
+        .enumerate()
+        .map(|(i, &fill_qty)| {
+            let order_map = order_map.clone();
+            async move {
+                fill_order(&order_map, i, fill_qty);
+            }
+        })
+        .collect();
+
+    join_all(update_tasks).await;
+}
+
+/// Same operations as [`async_order_operations`] with each phase's futures
+/// driven by `tasks`. The futures own their data so they can be spawned.
+pub async fn spawned_order_operations(workload: &OrderWorkload, tasks: TaskStrategy) {
+    let order_map: Arc<DashMap<String, Order>> = Arc::new(DashMap::new());
+
+    // Write operations - Create orders
+    let write_tasks: Vec<_> = workload
+        .orders
+        .iter()
+        .enumerate()
+        .map(|(i, order)| {
+            let order_map = order_map.clone();
+            let order = order.clone();
+            async move {
+                insert_order(&order_map, i, order);
+            }
+        })
+        .collect();
+
+    run_tasks(tasks, write_tasks).await;
+
+    // Read operations - Query orders
+    let read_tasks: Vec<_> = (0..workload.len())
+        .map(|i| {
+            let order_map = order_map.clone();
+            async move {
+                black_box(query_order(&order_map, i));
+            }
+        })
+        .collect();
+
+    run_tasks(tasks, read_tasks).await;
+
+    // Update operations - Fill orders
+    let update_tasks: Vec<_> = workload
+        .fills
+        .iter()
+        .enumerate()
+        .map(|(i, &fill_qty)| {
+            let order_map = order_map.clone();
+            async move {
+                fill_order(&order_map, i, fill_qty);
+            }
+        })
+        .collect();
+
+    run_tasks(tasks, update_tasks).await;
+}
+
+/// Fewest timed runs a [`ScenarioRunner`] takes; a standard deviation needs
+/// at least two samples.
+pub const MIN_TIMED_RUNS: usize = 2;
+
+/// Times scenarios outside Criterion: a few untimed warm-up runs, then one
+/// sample per timed run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScenarioRunner {
+    warmup_runs: usize,
+    timed_runs: usize,
+}
+
+impl ScenarioRunner {
+    /// Fails when `timed_runs` is below [`MIN_TIMED_RUNS`].
+    pub fn new(warmup_runs: usize, timed_runs: usize) -> Result<Self, String> {
+        if timed_runs < MIN_TIMED_RUNS {
+            return Err(format!("at least {} timed runs are needed, got {}", MIN_TIMED_RUNS, timed_runs));
+        }
+        Ok(Self { warmup_runs, timed_runs })
+    }
+
+    pub fn warmup_runs(&self) -> usize {
+        self.warmup_runs
+    }
+
+    pub fn timed_runs(&self) -> usize {
+        self.timed_runs
+    }
+
+    /// Wall-clock time of each timed run in nanoseconds.
+    pub fn samples(&self, scenario: Scenario, tasks: TaskStrategy, workload: &OrderWorkload, runtime: &Runtime) -> Vec<f64> {
+        for _ in 0..self.warmup_runs {
+            scenario.execute_with(tasks, workload, runtime);
+        }
+        (0..self.timed_runs)
+            .map(|_| {
+                let start = Instant::now();
+                scenario.execute_with(tasks, workload, runtime);
+                start.elapsed().as_nanos() as f64
+            })
+            .collect()
+    }
+
+    /// Runs `scenario` and summarizes the samples the way `plot_results`
+    /// summarizes Criterion output: mean, median, standard deviation and a
+    /// normal-approximation 95% interval of the mean.
+    pub fn run(&self, scenario: Scenario, workload: &OrderWorkload, runtime: &Runtime) -> BenchmarkResult {
+        let settings = RuntimeSettings::default();
+        self.run_on(scenario.benchmark_id().0, scenario, TaskStrategy::JoinAll, &settings, workload, runtime)
+    }
+
+    /// Same as [`ScenarioRunner::run`] under a scenario file group, with the
+    /// futures driven by `tasks` on `runtime` built from `settings`. Tokio
+    /// results are named and typed the way the benchmarks name them.
+    pub fn run_on(
+        &self,
+        group: &str,
+        scenario: Scenario,
+        tasks: TaskStrategy,
+        settings: &RuntimeSettings,
+        workload: &OrderWorkload,
+        runtime: &Runtime,
+    ) -> BenchmarkResult {
+        let samples = self.samples(scenario, tasks, workload, runtime);
+        let count = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / count;
+        let variance = samples.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / (count - 1.0);
+        let std_dev = variance.sqrt();
+        let half_width = Z_95 * std_dev / count.sqrt();
+
+        let mut sorted = samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        } else {
+            sorted[middle]
+        };
+
+        let (_, function) = scenario.benchmark_id();
+        let (function, operation_type) = if scenario == Scenario::Tokio {
+            let base = match tasks {
+                TaskStrategy::JoinAll => scenario.operation_type().to_string(),
+                tasks => format!("{} {}", scenario.operation_type(), tasks.operation_name()),
+            };
+            (
+                format!("{}{}{}", function, tasks.suffix(), settings.label()),
+                settings.operation_type(&base),
+            )
+        } else {
+            (function.to_string(), scenario.operation_type().to_string())
+        };
+        BenchmarkResult::new(
+            format!("{}/{}/{}", group, function, workload.len()),
+            operation_type,
+            workload.len(),
+            mean,
+        )
+        .with_uncertainty(median, std_dev, mean - half_width, mean + half_width)
+        .with_samples(samples)
+    }
+}
+
+impl Default for ScenarioRunner {
+    fn default() -> Self {
+        Self {
+            warmup_runs: 3,
+            timed_runs: 20,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn runner_needs_two_timed_runs() {
+        assert!(ScenarioRunner::new(3, 0).is_err());
+        assert!(ScenarioRunner::new(3, 1).is_err());
+
+        let runner = ScenarioRunner::new(0, MIN_TIMED_RUNS).unwrap();
+        assert_eq!((runner.warmup_runs(), runner.timed_runs()), (0, MIN_TIMED_RUNS));
+    }
+
+    const STRATEGIES: [TaskStrategy; 7] = [
+        TaskStrategy::JoinAll,
+        TaskStrategy::Spawn,
+        TaskStrategy::JoinSet,
+        TaskStrategy::Chunked(1),
+        TaskStrategy::Chunked(3),
+        TaskStrategy::Chunked(64),
+        TaskStrategy::FuturesUnordered,
+    ];
+
+    #[test]
+    fn task_strategies_round_trip_through_names_and_suffixes() {
+        for tasks in STRATEGIES {
+            assert_eq!(TaskStrategy::parse(&tasks.to_string()), Some(tasks));
+            assert_eq!(TaskStrategy::try_from(String::from(tasks)), Ok(tasks));
+            let function = format!("async{}", tasks.suffix());
+            assert_eq!(TaskStrategy::strip_suffix(&function), ("async", tasks));
+        }
+        assert_eq!(TaskStrategy::parse(" Chunked:16 "), Some(TaskStrategy::Chunked(16)));
+        for invalid in ["chunked:0", "chunked:", "chunked:-1", "threads"] {
+            assert_eq!(TaskStrategy::parse(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn every_strategy_runs_each_future_once() {
+        let runtimes = [
+            RuntimeSettings::current_thread().build().unwrap(),
+            RuntimeSettings::multi_thread(2).build().unwrap(),
+        ];
+        for runtime in &runtimes {
+            for tasks in STRATEGIES {
+                let runs: Arc<Vec<AtomicUsize>> = Arc::new((0..10).map(|_| AtomicUsize::new(0)).collect());
+                let futures: Vec<_> = (0..runs.len())
+                    .map(|i| {
+                        let runs = runs.clone();
+                        async move {
+                            tokio::task::yield_now().await;
+                            runs[i].fetch_add(1, Ordering::Relaxed);
+                        }
+                    })
+                    .collect();
+                runtime.block_on(run_tasks(tasks, futures));
+
+                let counts: Vec<usize> = runs.iter().map(|count| count.load(Ordering::Relaxed)).collect();
+                assert_eq!(counts, vec![1; 10], "{}", tasks);
+            }
+        }
+    }
+
+    #[test]
+    fn order_operations_run_under_every_strategy() {
+        let workload = OrderWorkload::generate(7, 50, 5, 3);
+        let runtime = RuntimeSettings::multi_thread(2).build().unwrap();
+        for tasks in STRATEGIES {
+            Scenario::Tokio.execute_with(tasks, &workload, &runtime);
+        }
+    }
+}
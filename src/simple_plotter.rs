@@ -1,10 +1,99 @@
+use crate::environment::{self, Environment};
+use crate::findings::{key_findings, markdown_bold_to_html, recommendations, variant_name};
+use crate::history::RunRecord;
+use crate::latency::LatencySummary;
+use crate::svg_chart::{escape_xml, Point, SvgChart};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+const HTML_STYLE: &str = r#"
+body { font-family: sans-serif; margin: 40px; color: #222; }
+table { border-collapse: collapse; margin: 20px 0; }
+th, td { border: 1px solid #ddd; padding: 6px 10px; text-align: right; }
+th:first-child, td:first-child { text-align: left; }
+th { background-color: #f2f2f2; }
+table.sortable th { cursor: pointer; user-select: none; }
+table.sortable th[data-order="asc"]::after { content: " ▲"; }
+table.sortable th[data-order="desc"]::after { content: " ▼"; }
+.chart { margin: 20px 0; max-width: 100%; overflow-x: auto; }
+.controls { display: flex; flex-wrap: wrap; gap: 8px 24px; padding: 12px; background-color: #f8f8f8; border: 1px solid #ddd; }
+.controls fieldset { border: none; margin: 0; padding: 0; }
+.controls legend { font-weight: bold; padding: 0; margin-bottom: 4px; }
+.controls label { margin-right: 12px; white-space: nowrap; }
+"#;
+
+// Filters and table sorting for the HTML report. Plain DOM code so the
+// report works offline without any external library.
+const HTML_SCRIPT: &str = r#"
+(function () {
+  const data = JSON.parse(document.getElementById('benchmark-data').textContent);
+  const hiddenTypes = new Set();
+  const hiddenVariants = new Set();
+
+  function visible(operationType) {
+    const variant = data.variants[operationType] || 'other';
+    return !hiddenTypes.has(operationType) && !hiddenVariants.has(variant);
+  }
+
+  function apply() {
+    document.querySelectorAll('tr[data-op]').forEach(function (row) {
+      row.style.display = visible(row.dataset.op) ? '' : 'none';
+    });
+    document.querySelectorAll('g.series').forEach(function (series) {
+      series.style.display = visible(series.dataset.series) ? '' : 'none';
+    });
+  }
+
+  function bind(selector, hidden) {
+    document.querySelectorAll(selector).forEach(function (box) {
+      box.addEventListener('change', function () {
+        if (box.checked) {
+          hidden.delete(box.value);
+        } else {
+          hidden.add(box.value);
+        }
+        apply();
+      });
+    });
+  }
+  bind('input.type-filter', hiddenTypes);
+  bind('input.variant-filter', hiddenVariants);
+
+  document.querySelectorAll('table.sortable th').forEach(function (header) {
+    header.addEventListener('click', function () {
+      const table = header.closest('table');
+      const body = table.tBodies[0];
+      const index = header.cellIndex;
+      const ascending = header.dataset.order !== 'asc';
+      table.querySelectorAll('th').forEach(function (other) { delete other.dataset.order; });
+      header.dataset.order = ascending ? 'asc' : 'desc';
+
+      const key = function (row) {
+        const cell = row.cells[index];
+        return cell.dataset.value !== undefined ? parseFloat(cell.dataset.value) : cell.textContent;
+      };
+      const rows = Array.from(body.rows).sort(function (a, b) {
+        const x = key(a);
+        const y = key(b);
+        const order = typeof x === 'number' ? x - y : x.localeCompare(y);
+        return ascending ? order : -order;
+      });
+      rows.forEach(function (row) { body.appendChild(row); });
+    });
+  });
+})();
+"#;
+
+/// Escapes text for a Markdown table cell, where `|` would end the cell
+/// and a line break the row.
+pub(crate) fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BenchmarkResult {
     pub name: String,
     pub operation_type: String,
@@ -13,9 +102,26 @@ pub struct BenchmarkResult {
     pub time_us: f64,
     pub time_ms: f64,
     pub throughput_ops_per_sec: f64,
+    /// Mean time of the previous (`base`) Criterion run, when there was one.
+    #[serde(default)]
+    pub base_time_ns: Option<f64>,
+    #[serde(default)]
+    pub median_ns: f64,
+    #[serde(default)]
+    pub std_dev_ns: f64,
+    /// Bounds of the 95% confidence interval of the mean.
+    #[serde(default)]
+    pub ci_lower_ns: f64,
+    #[serde(default)]
+    pub ci_upper_ns: f64,
+    /// Per-iteration times of each Criterion sample.
+    #[serde(default)]
+    pub samples_ns: Vec<f64>,
 }
 
 impl BenchmarkResult {
+    /// A result with only a mean; the spread is zero until
+    /// [`with_uncertainty`](Self::with_uncertainty) fills it in.
     pub fn new(name: String, operation_type: String, data_size: usize, time_ns: f64) -> Self {
         let time_us = time_ns / 1_000.0;
         let time_ms = time_ns / 1_000_000.0;
@@ -29,25 +135,186 @@ impl BenchmarkResult {
             time_us,
             time_ms,
             throughput_ops_per_sec,
+            base_time_ns: None,
+            median_ns: time_ns,
+            std_dev_ns: 0.0,
+            ci_lower_ns: time_ns,
+            ci_upper_ns: time_ns,
+            samples_ns: Vec::new(),
+        }
+    }
+    
+    pub fn with_uncertainty(mut self, median_ns: f64, std_dev_ns: f64, ci_lower_ns: f64, ci_upper_ns: f64) -> Self {
+        self.median_ns = median_ns;
+        self.std_dev_ns = std_dev_ns;
+        self.ci_lower_ns = ci_lower_ns;
+        self.ci_upper_ns = ci_upper_ns;
+        self
+    }
+    
+    pub fn with_samples(mut self, samples_ns: Vec<f64>) -> Self {
+        self.samples_ns = samples_ns;
+        self
+    }
+    
+    /// Half the width of the confidence interval as a percentage of the mean.
+    pub fn relative_error_pct(&self) -> f64 {
+        if self.time_ns == 0.0 {
+            0.0
+        } else {
+            (self.ci_upper_ns - self.ci_lower_ns) / 2.0 / self.time_ns * 100.0
+        }
+    }
+}
+
+/// Schema version written by [`SimplePlotter::export_json`]. Bump it when a
+/// field is removed or changes meaning; adding optional fields does not.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// A result together with the metrics derived from it, as exported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedResult {
+    #[serde(flatten)]
+    pub result: BenchmarkResult,
+    pub per_order_ns: f64,
+    pub relative_error_pct: f64,
+}
+
+/// Document written by [`SimplePlotter::export_json`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultsDocument {
+    pub schema_version: u32,
+    pub generated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub environment: Option<Environment>,
+    pub results: Vec<ExportedResult>,
+    #[serde(default)]
+    pub latencies: Vec<LatencySummary>,
+}
+
+impl ResultsDocument {
+    /// Reads a document, rejecting schema versions newer than this build
+    /// understands.
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let document: ResultsDocument = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if document.schema_version > JSON_SCHEMA_VERSION {
+            return Err(format!(
+                "{} uses results schema version {}, this build reads up to {}",
+                path, document.schema_version, JSON_SCHEMA_VERSION
+            ).into());
+        }
+        Ok(document)
+    }
+}
+
+/// Output files of [`SimplePlotter::generate_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// `benchmark_results.csv`, plus `latency_percentiles.csv` when there
+    /// are latency summaries.
+    Csv,
+    Json,
+    Markdown,
+    Html,
+    /// `benchmark_analysis.txt`
+    Text,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 5] = [
+        ReportFormat::Csv,
+        ReportFormat::Json,
+        ReportFormat::Markdown,
+        ReportFormat::Html,
+        ReportFormat::Text,
+    ];
+
+    pub fn parse(value: &str) -> Option<ReportFormat> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "csv" => Some(ReportFormat::Csv),
+            "json" => Some(ReportFormat::Json),
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            "html" => Some(ReportFormat::Html),
+            "txt" | "text" => Some(ReportFormat::Text),
+            _ => None,
         }
     }
 }
 
+// One row of benchmark_results.csv. Columns added after the first release
+// are optional so older files still load.
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRow {
+    #[serde(default)]
+    timestamp: String,
+    seed: Option<u64>,
+    name: String,
+    operation_type: String,
+    data_size: usize,
+    time_ns: f64,
+    time_us: f64,
+    time_ms: f64,
+    throughput_ops_per_sec: f64,
+    per_order_ns: f64,
+    median_ns: Option<f64>,
+    std_dev_ns: Option<f64>,
+    ci_lower_ns: Option<f64>,
+    ci_upper_ns: Option<f64>,
+    sample_count: Option<usize>,
+}
+
+// One row of latency_percentiles.csv
+#[derive(Debug, Serialize)]
+struct LatencyCsvRow<'a> {
+    scenario: &'a str,
+    operation: String,
+    count: u64,
+    mean_ns: f64,
+    p50_ns: u64,
+    p90_ns: u64,
+    p99_ns: u64,
+    p999_ns: u64,
+    max_ns: u64,
+}
+
 pub struct SimplePlotter {
     pub results: Vec<BenchmarkResult>,
+    pub seed: Option<u64>,
+    pub latencies: Vec<LatencySummary>,
+    pub environment: Option<Environment>,
 }
 
 impl SimplePlotter {
     pub fn new() -> Self {
         Self {
             results: Vec::new(),
+            seed: None,
+            latencies: Vec::new(),
+            environment: None,
         }
     }
     
+    /// Records the workload seed the results were produced with.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+    
+    /// Records the machine and build the results were produced on.
+    pub fn set_environment(&mut self, environment: Environment) {
+        self.environment = Some(environment);
+    }
+    
     pub fn add_result(&mut self, result: BenchmarkResult) {
         self.results.push(result);
     }
     
+    /// Adds per-operation latency percentiles captured alongside the means.
+    pub fn add_latency_summaries(&mut self, summaries: Vec<LatencySummary>) {
+        self.latencies.extend(summaries);
+    }
+    
     pub fn add_sample_results(&mut self) {
         // Add sample results based on our benchmark data
         let sample_data = vec![
@@ -70,22 +337,26 @@ impl SimplePlotter {
     }
     
     pub fn print_ascii_chart(&self) {
-        println!("\n📊 HFT ORDER PROCESSING PERFORMANCE CHART");
-        println!("═══════════════════════════════════════════════════════════════");
+        self.write_ascii_chart(&mut std::io::stdout()).expect("failed to write to stdout");
+    }
+    
+    pub fn write_ascii_chart(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(out, "\n📊 HFT ORDER PROCESSING PERFORMANCE CHART")?;
+        writeln!(out, "═══════════════════════════════════════════════════════════════")?;
         
         // Group by operation type
-        let mut grouped: HashMap<String, Vec<&BenchmarkResult>> = HashMap::new();
+        let mut grouped: BTreeMap<String, Vec<&BenchmarkResult>> = BTreeMap::new();
         for result in &self.results {
             grouped.entry(result.operation_type.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(result);
         }
         
         for (op_type, mut results) in grouped {
-            results.sort_by(|a, b| a.data_size.cmp(&b.data_size));
+            results.sort_by_key(|r| r.data_size);
             
-            println!("\n🔸 {}", op_type);
-            println!("{}", "─".repeat(50));
+            writeln!(out, "\n🔸 {}", op_type)?;
+            writeln!(out, "{}", "─".repeat(50))?;
             
             // Create ASCII bar chart
             let max_time = results.iter().map(|r| r.time_us).fold(0.0, f64::max);
@@ -96,26 +367,33 @@ impl SimplePlotter {
                 let bar = "█".repeat(bar_length);
                 let per_order_ns = result.time_ns / result.data_size as f64;
                 
-                println!("{:>6} orders │{:<40}│ {:>8.1}µs ({:>4.0}ns/order)",
+                writeln!(out, "{:>6} orders │{:<40}│ {:>8.1}µs ±{:>4.1}% ({:>4.0}ns/order)",
                     result.data_size,
                     bar,
                     result.time_us,
+                    result.relative_error_pct(),
                     per_order_ns
-                );
+                )?;
             }
         }
         
-        println!("\n📏 Scale: Each █ represents {:.1}µs", 40.0 / (self.results.iter().map(|r| r.time_us).fold(0.0, f64::max) / 40.0));
+        writeln!(out, "\n📏 Scale: Each █ represents {:.1}µs", 40.0 / (self.results.iter().map(|r| r.time_us).fold(0.0, f64::max) / 40.0))?;
+        
+        Ok(())
     }
     
     pub fn print_comparison_table(&self) {
-        println!("\n📋 DETAILED PERFORMANCE COMPARISON");
-        println!("══════════════════════════════════════════════════════════════════════════════");
-        println!("{:<20} │ {:>8} │ {:>10} │ {:>12} │ {:>12} │ {:>10}",
-            "Operation Type", "Orders", "Latency", "Per-Order", "Throughput", "Efficiency");
-        println!("{:<20} │ {:>8} │ {:>10} │ {:>12} │ {:>12} │ {:>10}",
-            "", "", "(µs)", "(ns)", "(Mops/sec)", "Score");
-        println!("{}", "─".repeat(86));
+        self.write_comparison_table(&mut std::io::stdout()).expect("failed to write to stdout");
+    }
+    
+    pub fn write_comparison_table(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(out, "\n📋 DETAILED PERFORMANCE COMPARISON")?;
+        writeln!(out, "══════════════════════════════════════════════════════════════════════════════")?;
+        writeln!(out, "{:<20} │ {:>8} │ {:>10} │ {:>8} │ {:>10} │ {:>10} │ {:>12} │ {:>12} │ {:>10}",
+            "Operation Type", "Orders", "Latency", "95% CI", "Median", "Std Dev", "Per-Order", "Throughput", "Efficiency")?;
+        writeln!(out, "{:<20} │ {:>8} │ {:>10} │ {:>8} │ {:>10} │ {:>10} │ {:>12} │ {:>12} │ {:>10}",
+            "", "", "(µs)", "(±%)", "(µs)", "(µs)", "(ns)", "(Mops/sec)", "Score")?;
+        writeln!(out, "{}", "─".repeat(125))?;
         
         let mut sorted_results = self.results.clone();
         sorted_results.sort_by(|a, b| {
@@ -128,36 +406,46 @@ impl SimplePlotter {
             let throughput_mops = result.throughput_ops_per_sec / 1_000_000.0;
             let efficiency_score = 1000.0 / per_order_ns; // Higher is better
             
-            println!("{:<20} │ {:>8} │ {:>10.1} │ {:>12.0} │ {:>12.1} │ {:>10.2}",
+            writeln!(out, "{:<20} │ {:>8} │ {:>10.1} │ {:>8.1} │ {:>10.1} │ {:>10.1} │ {:>12.0} │ {:>12.1} │ {:>10.2}",
                 result.operation_type,
                 result.data_size,
                 result.time_us,
+                result.relative_error_pct(),
+                result.median_ns / 1_000.0,
+                result.std_dev_ns / 1_000.0,
                 per_order_ns,
                 throughput_mops,
                 efficiency_score
-            );
+            )?;
         }
         
-        println!("\n💡 Efficiency Score: Higher = Better (1000/ns_per_order)");
+        writeln!(out, "\n💡 Efficiency Score: Higher = Better (1000/ns_per_order)")?;
+        writeln!(out, "📐 95% CI: half-width of the confidence interval of the mean, relative to the mean")?;
+        
+        Ok(())
     }
     
     pub fn print_scalability_analysis(&self) {
-        println!("\n📈 SCALABILITY ANALYSIS");
-        println!("═════════════════════════════════════════════════════════════");
+        self.write_scalability_analysis(&mut std::io::stdout()).expect("failed to write to stdout");
+    }
+    
+    pub fn write_scalability_analysis(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(out, "\n📈 SCALABILITY ANALYSIS")?;
+        writeln!(out, "═════════════════════════════════════════════════════════════")?;
         
-        let mut grouped: HashMap<String, Vec<&BenchmarkResult>> = HashMap::new();
+        let mut grouped: BTreeMap<String, Vec<&BenchmarkResult>> = BTreeMap::new();
         for result in &self.results {
             grouped.entry(result.operation_type.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(result);
         }
         
         for (op_type, mut results) in grouped {
-            results.sort_by(|a, b| a.data_size.cmp(&b.data_size));
+            results.sort_by_key(|r| r.data_size);
             
             if results.len() >= 2 {
-                println!("\n🔸 {} Scalability", op_type);
-                println!("{}", "─".repeat(40));
+                writeln!(out, "\n🔸 {} Scalability", op_type)?;
+                writeln!(out, "{}", "─".repeat(40))?;
                 
                 let first = results[0];
                 let last = results[results.len() - 1];
@@ -166,11 +454,17 @@ impl SimplePlotter {
                 let time_ratio = last.time_us / first.time_us;
                 let scalability_factor = time_ratio / size_ratio;
                 
-                println!("Size increase: {}x ({} → {} orders)", 
-                    size_ratio as usize, first.data_size, last.data_size);
-                println!("Time increase: {:.1}x ({:.1}µs → {:.1}µs)", 
-                    time_ratio, first.time_us, last.time_us);
-                println!("Scalability factor: {:.2}", scalability_factor);
+                writeln!(out, "Size increase: {}x ({} → {} orders)", 
+                    size_ratio as usize, first.data_size, last.data_size)?;
+                // Widest ratio the two confidence intervals allow
+                let time_ratio_low = last.ci_lower_ns / first.ci_upper_ns;
+                let time_ratio_high = last.ci_upper_ns / first.ci_lower_ns;
+                
+                writeln!(out, "Time increase: {:.1}x [{:.1}x – {:.1}x] ({:.1}µs ±{:.1}% → {:.1}µs ±{:.1}%)", 
+                    time_ratio, time_ratio_low, time_ratio_high,
+                    first.time_us, first.relative_error_pct(),
+                    last.time_us, last.relative_error_pct())?;
+                writeln!(out, "Scalability factor: {:.2}", scalability_factor)?;
                 
                 let scalability_rating = match scalability_factor {
                     f if f < 1.2 => "🟢 Excellent (Sub-linear)",
@@ -179,56 +473,194 @@ impl SimplePlotter {
                     _ => "🔴 Poor (Exponential)"
                 };
                 
-                println!("Rating: {}", scalability_rating);
+                writeln!(out, "Rating: {}", scalability_rating)?;
             }
         }
+        
+        Ok(())
     }
     
-    pub fn export_csv(&self, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut file = File::create(output_path)?;
+    pub fn print_latency_percentiles(&self) {
+        self.write_latency_percentiles(&mut std::io::stdout()).expect("failed to write to stdout");
+    }
+    
+    pub fn write_latency_percentiles(&self, out: &mut impl Write) -> std::io::Result<()> {
+        if self.latencies.is_empty() {
+            return Ok(());
+        }
         
-        // Write CSV header
-        writeln!(file, "timestamp,name,operation_type,data_size,time_ns,time_us,time_ms,throughput_ops_per_sec,per_order_ns")?;
+        writeln!(out, "\n⏱️  PER-OPERATION LATENCY PERCENTILES")?;
+        writeln!(out, "══════════════════════════════════════════════════════════════════════════════════════════════")?;
+        writeln!(out, "{:<45} │ {:<6} │ {:>8} │ {:>8} │ {:>8} │ {:>8} │ {:>9}",
+            "Scenario", "Op", "p50 (ns)", "p90 (ns)", "p99 (ns)", "p99.9", "max (ns)")?;
+        writeln!(out, "{}", "─".repeat(110))?;
         
-        let timestamp = Utc::now();
-        for result in &self.results {
-            let per_order_ns = result.time_ns / result.data_size as f64;
-            writeln!(file, "{},{},{},{},{},{},{},{},{}",
-                timestamp.format("%Y-%m-%d %H:%M:%S"),
-                result.name,
-                result.operation_type,
-                result.data_size,
-                result.time_ns,
-                result.time_us,
-                result.time_ms,
-                result.throughput_ops_per_sec,
-                per_order_ns
+        for summary in &self.latencies {
+            writeln!(out, "{:<45} │ {:<6} │ {:>8} │ {:>8} │ {:>8} │ {:>8} │ {:>9}",
+                summary.scenario,
+                summary.operation.to_string(),
+                summary.p50_ns,
+                summary.p90_ns,
+                summary.p99_ns,
+                summary.p999_ns,
+                summary.max_ns
             )?;
         }
         
+        Ok(())
+    }
+    
+    pub fn export_latency_csv(&self, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = csv::Writer::from_path(output_path)?;
+        for summary in &self.latencies {
+            writer.serialize(LatencyCsvRow {
+                scenario: &summary.scenario,
+                operation: summary.operation.to_string(),
+                count: summary.count,
+                mean_ns: (summary.mean_ns * 10.0).round() / 10.0,
+                p50_ns: summary.p50_ns,
+                p90_ns: summary.p90_ns,
+                p99_ns: summary.p99_ns,
+                p999_ns: summary.p999_ns,
+                max_ns: summary.max_ns,
+            })?;
+        }
+        writer.flush()?;
+        
+        println!("📄 Latency percentiles exported to: {}", output_path);
+        Ok(())
+    }
+    
+    pub fn to_json_document(&self) -> ResultsDocument {
+        ResultsDocument {
+            schema_version: JSON_SCHEMA_VERSION,
+            generated_at: Utc::now(),
+            seed: self.seed,
+            environment: self.environment.clone(),
+            results: self.results
+                .iter()
+                .map(|result| ExportedResult {
+                    result: result.clone(),
+                    per_order_ns: result.time_ns / result.data_size as f64,
+                    relative_error_pct: result.relative_error_pct(),
+                })
+                .collect(),
+            latencies: self.latencies.clone(),
+        }
+    }
+    
+    pub fn export_json(&self, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(&self.to_json_document())?;
+        std::fs::write(output_path, json)?;
+        println!("🧾 JSON results exported to: {}", output_path);
+        Ok(())
+    }
+    
+    /// Plotter holding everything from a file written by [`Self::export_json`].
+    pub fn import_json(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let document = ResultsDocument::load(path)?;
+        Ok(Self {
+            results: document.results.into_iter().map(|exported| exported.result).collect(),
+            seed: document.seed,
+            latencies: document.latencies,
+            environment: document.environment,
+        })
+    }
+    
+    /// Writes the results as a plain CSV table. The environment, which has
+    /// no place in it, goes to a sidecar JSON file next to it.
+    pub fn export_csv(&self, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::path::Path::new(output_path);
+        match &self.environment {
+            Some(environment) => environment.record_for_csv(path)?,
+            // Don't leave an older run's environment next to these results
+            None if environment::csv_sidecar_path(path).exists() => std::fs::remove_file(environment::csv_sidecar_path(path))?,
+            None => {}
+        }
+        
+        let mut writer = csv::Writer::from_path(output_path)?;
+        let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        for result in &self.results {
+            writer.serialize(CsvRow {
+                timestamp: timestamp.clone(),
+                seed: self.seed,
+                name: result.name.clone(),
+                operation_type: result.operation_type.clone(),
+                data_size: result.data_size,
+                time_ns: result.time_ns,
+                time_us: result.time_us,
+                time_ms: result.time_ms,
+                throughput_ops_per_sec: result.throughput_ops_per_sec,
+                per_order_ns: result.time_ns / result.data_size as f64,
+                median_ns: Some(result.median_ns),
+                std_dev_ns: Some(result.std_dev_ns),
+                ci_lower_ns: Some(result.ci_lower_ns),
+                ci_upper_ns: Some(result.ci_upper_ns),
+                sample_count: Some(result.samples_ns.len()),
+            })?;
+        }
+        writer.flush()?;
+        
         println!("📄 CSV data exported to: {}", output_path);
         Ok(())
     }
     
+    /// Plotter holding the results of a file written by [`Self::export_csv`]
+    /// and the environment from its sidecar, including files from before
+    /// the uncertainty columns existed. Derived
+    /// columns are recomputed from `time_ns`; individual samples are not
+    /// stored in the CSV and come back empty.
+    pub fn import_csv(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut reader = csv::Reader::from_path(path)?;
+        let mut plotter = Self::new();
+        
+        for row in reader.deserialize() {
+            let row: CsvRow = row?;
+            if let Some(seed) = row.seed {
+                plotter.set_seed(seed);
+            }
+            let result = BenchmarkResult::new(row.name, row.operation_type, row.data_size, row.time_ns)
+                .with_uncertainty(
+                    row.median_ns.unwrap_or(row.time_ns),
+                    row.std_dev_ns.unwrap_or(0.0),
+                    row.ci_lower_ns.unwrap_or(row.time_ns),
+                    row.ci_upper_ns.unwrap_or(row.time_ns),
+                );
+            plotter.add_result(result);
+        }
+        
+        if let Some(environment) = Environment::load_for_csv(std::path::Path::new(path)) {
+            plotter.set_environment(environment);
+        }
+        Ok(plotter)
+    }
+    
     pub fn generate_markdown_report(&self, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let mut file = File::create(output_path)?;
         let timestamp = Utc::now();
         
         writeln!(file, "# HFT Order Processing Benchmark Report")?;
         writeln!(file, "\nGenerated on: {}\n", timestamp.format("%Y-%m-%d %H:%M:%S UTC"))?;
+        if let Some(seed) = self.seed {
+            writeln!(file, "Workload seed: {}\n", seed)?;
+        }
         
         writeln!(file, "## Executive Summary\n")?;
         writeln!(file, "This report analyzes the performance of different order processing approaches for High-Frequency Trading (HFT) systems.\n")?;
         
         writeln!(file, "### Key Findings\n")?;
-        writeln!(file, "- **Sync operations** are consistently 40-60% faster than async operations")?;
-        writeln!(file, "- **Concurrent operations** add ~15-30% overhead but provide thread safety")?;
-        writeln!(file, "- **Per-order latency** ranges from 465ns (sync) to 1050ns (async)")?;
-        writeln!(file, "- **Throughput** scales linearly with order volume\n")?;
+        let findings = key_findings(&self.results);
+        if findings.is_empty() {
+            writeln!(file, "- Not enough results to draw comparative findings")?;
+        }
+        for finding in findings {
+            writeln!(file, "- {}", finding)?;
+        }
+        writeln!(file)?;
         
         writeln!(file, "## Performance Results\n")?;
-        writeln!(file, "| Operation Type | Orders | Latency (µs) | Per-Order (ns) | Throughput (Mops/sec) |")?;
-        writeln!(file, "|----------------|--------|--------------|----------------|---------------------|")?;
+        writeln!(file, "| Operation Type | Orders | Latency (µs) | 95% CI (µs) | Median (µs) | Std Dev (µs) | Per-Order (ns) | Throughput (Mops/sec) |")?;
+        writeln!(file, "|----------------|--------|--------------|-------------|-------------|--------------|----------------|---------------------|")?;
         
         let mut sorted_results = self.results.clone();
         sorted_results.sort_by(|a, b| {
@@ -240,65 +672,425 @@ impl SimplePlotter {
             let per_order_ns = result.time_ns / result.data_size as f64;
             let throughput_mops = result.throughput_ops_per_sec / 1_000_000.0;
             
-            writeln!(file, "| {} | {} | {:.1} | {:.0} | {:.1} |",
-                result.operation_type,
+            writeln!(file, "| {} | {} | {:.1} ± {:.1}% | {:.1} – {:.1} | {:.1} | {:.1} | {:.0} | {:.1} |",
+                markdown_cell(&result.operation_type),
                 result.data_size,
                 result.time_us,
+                result.relative_error_pct(),
+                result.ci_lower_ns / 1_000.0,
+                result.ci_upper_ns / 1_000.0,
+                result.median_ns / 1_000.0,
+                result.std_dev_ns / 1_000.0,
                 per_order_ns,
                 throughput_mops
             )?;
         }
         
-        writeln!(file, "\n## Architecture Recommendations\n")?;
-        writeln!(file, "### For Ultra-Low Latency (< 1µs)\n")?;
-        writeln!(file, "- Use sync operations with single-threaded HashMap")?;
-        writeln!(file, "- Minimize allocations and string operations")?;
-        writeln!(file, "- Pre-allocate order pools\n")?;
+        writeln!(file, "\nLatency is the mean with the half-width of its 95% confidence interval.")?;
         
-        writeln!(file, "### For High Throughput (1M+ orders/sec)\n")?;
-        writeln!(file, "- Use concurrent operations with DashMap")?;
-        writeln!(file, "- Implement order batching and periodic cleanup")?;
-        writeln!(file, "- Consider lock-free data structures\n")?;
+        if !self.latencies.is_empty() {
+            writeln!(file, "\n## Per-Operation Latency Percentiles\n")?;
+            writeln!(file, "Each insert, get and update of the order operation benchmarks, and each open-loop `order` request from its intended send time, timed individually and recorded in an HDR histogram.\n")?;
+            writeln!(file, "| Scenario | Operation | Samples | p50 (ns) | p90 (ns) | p99 (ns) | p99.9 (ns) | max (ns) |")?;
+            writeln!(file, "|----------|-----------|---------|----------|----------|----------|------------|----------|")?;
+            
+            for summary in &self.latencies {
+                writeln!(file, "| {} | {} | {} | {} | {} | {} | {} | {} |",
+                    markdown_cell(&summary.scenario),
+                    summary.operation,
+                    summary.count,
+                    summary.p50_ns,
+                    summary.p90_ns,
+                    summary.p99_ns,
+                    summary.p999_ns,
+                    summary.max_ns
+                )?;
+            }
+        }
+        
+        if let Some(environment) = &self.environment {
+            writeln!(file, "\n## Environment\n")?;
+            writeln!(file, "| Property | Value |")?;
+            writeln!(file, "|----------|-------|")?;
+            for (label, value) in environment.fields() {
+                writeln!(file, "| {} | {} |", markdown_cell(label), markdown_cell(&value))?;
+            }
+        }
         
-        writeln!(file, "### For Scalable Systems (Multi-client)\n")?;
-        writeln!(file, "- Use async operations for client handling")?;
-        writeln!(file, "- Sync operations for order matching core")?;
-        writeln!(file, "- Hybrid architecture with message passing\n")?;
+        writeln!(file, "\n## Architecture Recommendations\n")?;
+        let recommendations = recommendations(&self.results);
+        if recommendations.is_empty() {
+            writeln!(file, "- Not enough results to compare variants")?;
+        }
+        for recommendation in recommendations {
+            writeln!(file, "- {}", recommendation)?;
+        }
+        writeln!(file)?;
         
         println!("📝 Markdown report saved to: {}", output_path);
         Ok(())
     }
     
+    // One series per operation type; `value` maps a result to (y, low, high)
+    fn chart(&self, title: &str, y_label: &str, value: impl Fn(&BenchmarkResult) -> (f64, f64, f64)) -> SvgChart {
+        let mut grouped: BTreeMap<&str, Vec<Point>> = BTreeMap::new();
+        for result in &self.results {
+            let (y, low, high) = value(result);
+            grouped.entry(result.operation_type.as_str())
+                .or_default()
+                .push(Point::new(result.data_size as f64, y, low, high));
+        }
+        
+        let mut chart = SvgChart::new(title, "Number of orders", y_label);
+        for (op_type, points) in grouped {
+            chart.add_series(op_type, points);
+        }
+        chart
+    }
+    
+    /// Mean batch latency against batch size, with 95% CI error bars.
+    pub fn latency_chart(&self) -> SvgChart {
+        self.chart("Latency vs Batch Size", "Latency (µs)", |r| {
+            (r.time_us, r.ci_lower_ns / 1_000.0, r.ci_upper_ns / 1_000.0)
+        })
+    }
+    
+    pub fn throughput_chart(&self) -> SvgChart {
+        self.chart("Throughput vs Batch Size", "Orders/sec", |r| {
+            let size = r.data_size as f64 * 1_000_000_000.0;
+            (r.throughput_ops_per_sec, size / r.ci_upper_ns, size / r.ci_lower_ns)
+        })
+    }
+    
+    pub fn per_order_chart(&self) -> SvgChart {
+        self.chart("Per-Order Latency vs Batch Size", "ns per order", |r| {
+            let size = r.data_size as f64;
+            (r.time_ns / size, r.ci_lower_ns / size, r.ci_upper_ns / size)
+        })
+    }
+    
+    /// Single self-contained HTML file with the findings, inline SVG charts,
+    /// sortable result tables and operation type / variant filters. The
+    /// results are embedded as JSON; nothing is loaded from outside the file.
+    pub fn generate_html_report(&self, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut sorted_results = self.results.clone();
+        sorted_results.sort_by(|a, b| {
+            a.operation_type.cmp(&b.operation_type)
+                .then(a.data_size.cmp(&b.data_size))
+        });
+        
+        let variants: BTreeMap<&str, &str> = sorted_results
+            .iter()
+            .map(|r| (r.operation_type.as_str(), variant_name(&r.operation_type).unwrap_or("other")))
+            .collect();
+        
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<title>HFT Order Processing Benchmark Report</title>\n");
+        html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", HTML_STYLE));
+        
+        html.push_str("<h1>HFT Order Processing Benchmark Report</h1>\n");
+        html.push_str(&format!("<p>Generated on: {}</p>\n", Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+        if let Some(seed) = self.seed {
+            html.push_str(&format!("<p>Workload seed: {}</p>\n", seed));
+        }
+        
+        html.push_str("<h2>Key Findings</h2>\n<ul>\n");
+        let findings = key_findings(&self.results);
+        if findings.is_empty() {
+            html.push_str("<li>Not enough results to draw comparative findings</li>\n");
+        }
+        for finding in findings {
+            html.push_str(&format!("<li>{}</li>\n", markdown_bold_to_html(&finding)));
+        }
+        html.push_str("</ul>\n");
+        
+        // Filters apply to the charts and the results table
+        html.push_str("<div class=\"controls\">\n<fieldset>\n<legend>Variant</legend>\n");
+        for variant in ["sync", "concurrent", "async", "other"] {
+            if variants.values().any(|v| *v == variant) {
+                html.push_str(&format!(
+                    "<label><input type=\"checkbox\" class=\"variant-filter\" value=\"{0}\" checked> {0}</label>\n",
+                    variant
+                ));
+            }
+        }
+        html.push_str("</fieldset>\n<fieldset>\n<legend>Operation type</legend>\n");
+        for op_type in variants.keys() {
+            html.push_str(&format!(
+                "<label><input type=\"checkbox\" class=\"type-filter\" value=\"{0}\" checked> {0}</label>\n",
+                escape_xml(op_type)
+            ));
+        }
+        html.push_str("</fieldset>\n</div>\n");
+        
+        html.push_str("<h2>Charts</h2>\n");
+        html.push_str("<p>Both axes are logarithmic. Error bars span the 95% confidence interval of the mean.</p>\n");
+        for chart in [self.latency_chart(), self.throughput_chart(), self.per_order_chart()] {
+            html.push_str("<div class=\"chart\">\n");
+            html.push_str(&chart.to_svg());
+            html.push_str("</div>\n");
+        }
+        
+        html.push_str("<h2>Performance Results</h2>\n<p>Click a column header to sort.</p>\n<table class=\"sortable\">\n");
+        html.push_str("<thead><tr><th>Operation Type</th><th>Orders</th><th>Latency (µs)</th><th>95% CI (µs)</th><th>Median (µs)</th><th>Per-Order (ns)</th><th>Throughput (Mops/sec)</th></tr></thead>\n<tbody>\n");
+        for result in &sorted_results {
+            let per_order_ns = result.time_ns / result.data_size as f64;
+            let throughput_mops = result.throughput_ops_per_sec / 1_000_000.0;
+            html.push_str(&format!(
+                "<tr data-op=\"{0}\"><td>{0}</td><td data-value=\"{1}\">{1}</td><td data-value=\"{2}\">{2:.1} ± {3:.1}%</td><td data-value=\"{4}\">{4:.1} – {5:.1}</td><td data-value=\"{6}\">{6:.1}</td><td data-value=\"{7}\">{7:.0}</td><td data-value=\"{8}\">{8:.1}</td></tr>\n",
+                escape_xml(&result.operation_type),
+                result.data_size,
+                result.time_us,
+                result.relative_error_pct(),
+                result.ci_lower_ns / 1_000.0,
+                result.ci_upper_ns / 1_000.0,
+                result.median_ns / 1_000.0,
+                per_order_ns,
+                throughput_mops
+            ));
+        }
+        html.push_str("</tbody>\n</table>\n");
+        
+        if !self.latencies.is_empty() {
+            html.push_str("<h2>Per-Operation Latency Percentiles</h2>\n<table class=\"sortable\">\n");
+            html.push_str("<thead><tr><th>Scenario</th><th>Operation</th><th>Samples</th><th>p50 (ns)</th><th>p90 (ns)</th><th>p99 (ns)</th><th>p99.9 (ns)</th><th>max (ns)</th></tr></thead>\n<tbody>\n");
+            for summary in &self.latencies {
+                html.push_str(&format!("<tr><td>{}</td><td>{}</td>", escape_xml(&summary.scenario), summary.operation));
+                for value in [summary.count, summary.p50_ns, summary.p90_ns, summary.p99_ns, summary.p999_ns, summary.max_ns] {
+                    html.push_str(&format!("<td data-value=\"{0}\">{0}</td>", value));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</tbody>\n</table>\n");
+        }
+        
+        if let Some(environment) = &self.environment {
+            html.push_str("<h2>Environment</h2>\n<table>\n<tbody>\n");
+            for (label, value) in environment.fields() {
+                html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", label, escape_xml(&value)));
+            }
+            html.push_str("</tbody>\n</table>\n");
+        }
+        
+        // `</` is escaped so result names cannot close the script element
+        let data = serde_json::json!({
+            "seed": self.seed,
+            "environment": self.environment,
+            "variants": variants,
+            "results": sorted_results,
+            "latencies": self.latencies,
+        });
+        html.push_str(&format!(
+            "<script type=\"application/json\" id=\"benchmark-data\">{}</script>\n",
+            serde_json::to_string(&data)?.replace("</", "<\\/")
+        ));
+        html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", HTML_SCRIPT));
+        
+        std::fs::write(output_path, html)?;
+        println!("🌐 HTML report saved to: {}", output_path);
+        Ok(())
+    }
+    
+    // Mean time per operation_type/data_size in each run, None where a run
+    // did not include that benchmark
+    fn trend_rows(history: &[RunRecord]) -> BTreeMap<(String, usize), Vec<Option<f64>>> {
+        let mut rows: BTreeMap<(String, usize), Vec<Option<f64>>> = BTreeMap::new();
+        for (run_index, run) in history.iter().enumerate() {
+            for result in &run.results {
+                let row = rows
+                    .entry((result.operation_type.clone(), result.data_size))
+                    .or_insert_with(|| vec![None; history.len()]);
+                row[run_index] = Some(result.time_ns);
+            }
+        }
+        rows
+    }
+    
+    // Runs whose environment differs from the previous run that recorded one
+    fn environment_changes(history: &[RunRecord]) -> Vec<(usize, Vec<String>)> {
+        let mut changes = Vec::new();
+        let mut previous: Option<&Environment> = None;
+        for (index, run) in history.iter().enumerate() {
+            let Some(environment) = &run.environment else {
+                continue;
+            };
+            if let Some(previous) = previous {
+                let differences = previous.differences(environment);
+                if !differences.is_empty() {
+                    changes.push((index, differences));
+                }
+            }
+            previous = Some(environment);
+        }
+        changes
+    }
+    
+    // Change from the first to the last run that has a value
+    fn trend_change_pct(values: &[Option<f64>]) -> Option<f64> {
+        let first = values.iter().flatten().next()?;
+        let last = values.iter().flatten().last()?;
+        Some((last - first) / first * 100.0)
+    }
+    
+    /// ASCII chart, comparison table, scalability analysis and latency
+    /// percentiles, as printed by `generate_complete_report`.
+    pub fn write_analysis(&self, out: &mut impl Write) -> std::io::Result<()> {
+        self.write_ascii_chart(out)?;
+        self.write_comparison_table(out)?;
+        self.write_scalability_analysis(out)?;
+        self.write_latency_percentiles(out)
+    }
+    
+    /// [`Self::write_analysis`] as a `String`.
+    pub fn analysis_text(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write_analysis(&mut buffer).expect("writing to a Vec cannot fail");
+        String::from_utf8(buffer).expect("analysis is UTF-8")
+    }
+    
+    pub fn print_trend_report(&self, history: &[RunRecord]) {
+        self.write_trend_report(&mut std::io::stdout(), history).expect("failed to write to stdout");
+    }
+    
+    pub fn write_trend_report(&self, out: &mut impl Write, history: &[RunRecord]) -> std::io::Result<()> {
+        if history.is_empty() {
+            return Ok(());
+        }
+        
+        writeln!(out, "\n📉 TREND OVER LAST {} RUNS", history.len())?;
+        writeln!(out, "═════════════════════════════════════════════════════════════")?;
+        writeln!(out, "Runs: {}", history.iter().map(RunRecord::label).collect::<Vec<_>>().join(" → "))?;
+        for (run, changes) in Self::environment_changes(history) {
+            writeln!(out, "⚠️  Run {} ({}) ran in a different environment: {}", run + 1, history[run].label(), changes.join("; "))?;
+        }
+        
+        for ((op_type, data_size), values) in Self::trend_rows(history) {
+            let series: Vec<String> = values
+                .iter()
+                .map(|value| value.map(|ns| format!("{:.1}", ns / 1_000.0)).unwrap_or_else(|| "-".to_string()))
+                .collect();
+            let change = Self::trend_change_pct(&values)
+                .map(|pct| format!("{:+.1}%", pct))
+                .unwrap_or_default();
+            
+            writeln!(out, "{:<24} {:>6} orders: {} µs ({})", op_type, data_size, series.join(" → "), change)?;
+        }
+        
+        Ok(())
+    }
+    
+    pub fn generate_trend_report(&self, history: &[RunRecord], output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::create(output_path)?;
+        
+        writeln!(file, "# HFT Benchmark Trend Report")?;
+        writeln!(file, "\nGenerated on: {}\n", Utc::now().format("%Y-%m-%d %H:%M:%S UTC"))?;
+        
+        writeln!(file, "## Runs\n")?;
+        writeln!(file, "| Run | Timestamp | Commit | Host | Seed |")?;
+        writeln!(file, "|-----|-----------|--------|------|------|")?;
+        for (index, run) in history.iter().enumerate() {
+            writeln!(file, "| {} | {} | {} | {} | {} |",
+                index + 1,
+                run.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                markdown_cell(run.git_commit.as_deref().unwrap_or("-")),
+                markdown_cell(&run.host),
+                run.seed.map(|seed| seed.to_string()).unwrap_or_else(|| "-".to_string())
+            )?;
+        }
+        
+        let changes = Self::environment_changes(history);
+        if !changes.is_empty() {
+            writeln!(file, "\n> **Warning:** not every run used the same environment, so some changes may not be caused by the code.\n>")?;
+            for (run, differences) in changes {
+                writeln!(file, "> - Run {}: {}", run + 1, differences.join("; "))?;
+            }
+        }
+        
+        writeln!(file, "\n## Mean Latency per Run (µs)\n")?;
+        let labels: Vec<String> = history.iter().map(|run| markdown_cell(&run.label())).collect();
+        writeln!(file, "| Operation Type | Orders | {} | Change |", labels.join(" | "))?;
+        writeln!(file, "|----------------|--------|{}--------|", "------|".repeat(labels.len()))?;
+        
+        for ((op_type, data_size), values) in Self::trend_rows(history) {
+            let cells: Vec<String> = values
+                .iter()
+                .map(|value| value.map(|ns| format!("{:.1}", ns / 1_000.0)).unwrap_or_else(|| "-".to_string()))
+                .collect();
+            let change = Self::trend_change_pct(&values)
+                .map(|pct| format!("{:+.1}%", pct))
+                .unwrap_or_else(|| "-".to_string());
+            
+            writeln!(file, "| {} | {} | {} | {} |", markdown_cell(&op_type), data_size, cells.join(" | "), change)?;
+        }
+        
+        println!("📝 Trend report saved to: {}", output_path);
+        Ok(())
+    }
+    
     pub fn generate_complete_report(&self, output_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.generate_report(output_dir, &ReportFormat::ALL)
+    }
+    
+    /// Writes the files for `formats` into `output_dir` and prints the
+    /// analysis to the console.
+    pub fn generate_report(&self, output_dir: &str, formats: &[ReportFormat]) -> Result<(), Box<dyn std::error::Error>> {
         std::fs::create_dir_all(output_dir)?;
+        let mut created = Vec::new();
         
-        // Export CSV
-        self.export_csv(&format!("{}/benchmark_results.csv", output_dir))?;
-        
-        // Generate markdown report
-        self.generate_markdown_report(&format!("{}/benchmark_report.md", output_dir))?;
+        if formats.contains(&ReportFormat::Csv) {
+            self.export_csv(&format!("{}/benchmark_results.csv", output_dir))?;
+            created.push("benchmark_results.csv (raw data)");
+            if self.environment.is_some() {
+                created.push("benchmark_results.environment.json (environment of the CSV)");
+            }
+            if !self.latencies.is_empty() {
+                self.export_latency_csv(&format!("{}/latency_percentiles.csv", output_dir))?;
+                created.push("latency_percentiles.csv (p50/p90/p99/p99.9/max per operation)");
+            }
+        }
+        if formats.contains(&ReportFormat::Json) {
+            self.export_json(&format!("{}/benchmark_results.json", output_dir))?;
+            created.push("benchmark_results.json (versioned machine-readable results)");
+        }
+        if formats.contains(&ReportFormat::Markdown) {
+            self.generate_markdown_report(&format!("{}/benchmark_report.md", output_dir))?;
+            created.push("benchmark_report.md (detailed report)");
+        }
+        if formats.contains(&ReportFormat::Html) {
+            self.generate_html_report(&format!("{}/benchmark_report.html", output_dir))?;
+            created.push("benchmark_report.html (single-file report with SVG charts)");
+        }
         
-        // Print all analyses to console and save to text file
-        let text_output = format!("{}/benchmark_analysis.txt", output_dir);
-        let mut text_file = File::create(&text_output)?;
+        // Render the analysis once, then print it and save it to the text file
+        let mut analysis = Vec::new();
+        self.write_analysis(&mut analysis)?;
         
-        // Redirect stdout to capture prints
         println!("\n🎯 COMPLETE BENCHMARK ANALYSIS");
         println!("{}", "=".repeat(60));
+        std::io::stdout().write_all(&analysis)?;
         
-        self.print_comparison_table();
-        self.print_scalability_analysis(); 
-        
-        writeln!(text_file, "HFT Order Processing Benchmark Analysis")?;
-        writeln!(text_file, "Generated: {}", Utc::now().format("%Y-%m-%d %H:%M:%S UTC"))?;
-        writeln!(text_file, "\nSee benchmark_results.csv for raw data")?;
-        writeln!(text_file, "See benchmark_report.md for detailed report")?;
+        if formats.contains(&ReportFormat::Text) {
+            let mut text_file = File::create(format!("{}/benchmark_analysis.txt", output_dir))?;
+            writeln!(text_file, "HFT Order Processing Benchmark Analysis")?;
+            writeln!(text_file, "Generated: {}", Utc::now().format("%Y-%m-%d %H:%M:%S UTC"))?;
+            if let Some(seed) = self.seed {
+                writeln!(text_file, "Workload seed: {}", seed)?;
+            }
+            if let Some(environment) = &self.environment {
+                for (label, value) in environment.fields() {
+                    writeln!(text_file, "{}: {}", label, value)?;
+                }
+            }
+            text_file.write_all(&analysis)?;
+            created.push("benchmark_analysis.txt (full console analysis)");
+        }
         
-        println!("\n📁 Complete benchmark report generated in: {}", output_dir);
+        println!("\n📁 Benchmark report generated in: {}", output_dir);
         println!("📄 Files created:");
-        println!("   - benchmark_results.csv (raw data)");
-        println!("   - benchmark_report.md (detailed report)");
-        println!("   - benchmark_analysis.txt (analysis summary)");
+        for file in created {
+            println!("   - {}", file);
+        }
         
         Ok(())
     }
@@ -308,4 +1100,186 @@ impl Default for SimplePlotter {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::latency::Operation;
+    use std::path::PathBuf;
+
+    // benchmark_results.csv as written before the seed and uncertainty
+    // columns existed
+    const LEGACY_CSV: &str = "\
+timestamp,name,operation_type,data_size,time_ns,time_us,time_ms,throughput_ops_per_sec,per_order_ns
+2025-07-10 17:15:30,sync_order_operations/single_threaded,Sync Single,100,46500,46.5,0.0465,2150537.634408602,465
+2025-07-10 17:15:30,async_order_operations/async,Async,1000,1100000,1100,1.1,909090.9090909091,1100
+";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hft-plotter-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn plotter() -> SimplePlotter {
+        let mut plotter = SimplePlotter::new();
+        plotter.set_seed(42);
+        plotter.set_environment(Environment {
+            host: "bench-01".to_string(),
+            logical_cores: 8,
+            git_commit: Some("abc123".to_string()),
+            ..Environment::default()
+        });
+        plotter.add_result(
+            BenchmarkResult::new("async_order_operations/async/100".to_string(), "Async".to_string(), 100, 105_000.5)
+                .with_uncertainty(104_000.0, 1_250.25, 103_000.0, 107_000.0)
+                .with_samples(vec![104_000.0, 106_001.0]),
+        );
+        // Commas, quotes and pipes must survive both formats
+        plotter.add_result(BenchmarkResult::new(
+            "group/tokio \"spawn\", mt4|x/1000".to_string(),
+            "Async \"Spawn\" [multi_thread, 4 workers]".to_string(),
+            1000,
+            1_100_000.0,
+        ));
+        plotter.add_latency_summaries(vec![LatencySummary {
+            scenario: "async_order_operations/async/100".to_string(),
+            operation: Operation::Insert,
+            count: 100,
+            mean_ns: 250.5,
+            p50_ns: 200,
+            p90_ns: 400,
+            p99_ns: 900,
+            p999_ns: 1_500,
+            max_ns: 2_000,
+        }]);
+        plotter
+    }
+
+    #[test]
+    fn json_export_round_trips() {
+        let dir = temp_dir("json");
+        let path = dir.join("benchmark_results.json").to_string_lossy().into_owned();
+        let plotter = plotter();
+        plotter.export_json(&path).unwrap();
+
+        let imported = SimplePlotter::import_json(&path).unwrap();
+        assert_eq!(imported.results, plotter.results);
+        assert_eq!(imported.latencies, plotter.latencies);
+        assert_eq!(imported.seed, Some(42));
+        assert_eq!(imported.environment, plotter.environment);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn json_from_a_newer_schema_is_rejected() {
+        let dir = temp_dir("schema");
+        let path = dir.join("benchmark_results.json").to_string_lossy().into_owned();
+        let mut document = plotter().to_json_document();
+        document.schema_version = JSON_SCHEMA_VERSION + 1;
+        std::fs::write(&path, serde_json::to_string(&document).unwrap()).unwrap();
+
+        let err = ResultsDocument::load(&path).unwrap_err().to_string();
+        assert!(err.contains("schema version"), "{}", err);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn csv_export_round_trips_without_samples() {
+        let dir = temp_dir("csv");
+        let path = dir.join("benchmark_results.csv");
+        let plotter = plotter();
+        plotter.export_csv(&path.to_string_lossy()).unwrap();
+
+        // A plain table: the header comes first
+        let csv = std::fs::read_to_string(&path).unwrap();
+        assert!(csv.starts_with("timestamp,seed,name,"), "{}", csv);
+
+        let imported = SimplePlotter::import_csv(&path.to_string_lossy()).unwrap();
+        let expected: Vec<BenchmarkResult> = plotter
+            .results
+            .iter()
+            .cloned()
+            .map(|result| result.with_samples(Vec::new()))
+            .collect();
+        assert_eq!(imported.results, expected);
+        assert_eq!(imported.seed, Some(42));
+        assert_eq!(imported.environment, plotter.environment);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn loads_legacy_csv() {
+        let dir = temp_dir("legacy");
+        let path = dir.join("benchmark_results.csv");
+        std::fs::write(&path, LEGACY_CSV).unwrap();
+
+        let imported = SimplePlotter::import_csv(&path.to_string_lossy()).unwrap();
+        assert_eq!(imported.seed, None);
+        assert_eq!(imported.environment, None);
+        assert_eq!(
+            imported.results,
+            vec![
+                BenchmarkResult::new("sync_order_operations/single_threaded".to_string(), "Sync Single".to_string(), 100, 46_500.0),
+                BenchmarkResult::new("async_order_operations/async".to_string(), "Async".to_string(), 1000, 1_100_000.0),
+            ]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn markdown_report_escapes_cells() {
+        let dir = temp_dir("markdown");
+        let path = dir.join("benchmark_report.md").to_string_lossy().into_owned();
+        let mut plotter = plotter();
+        plotter.results[0].operation_type = "Async|JoinSet".to_string();
+        plotter.generate_markdown_report(&path).unwrap();
+
+        let report = std::fs::read_to_string(&path).unwrap();
+        assert!(report.contains("| Async\\|JoinSet | 100 |"), "{}", report);
+        assert!(!report.contains("| Async|JoinSet |"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn markdown_cells_escape_pipes_and_line_breaks() {
+        assert_eq!(markdown_cell("Async [multi_thread, 4 workers]"), "Async [multi_thread, 4 workers]");
+        assert_eq!(markdown_cell("group/a|b/100"), "group/a\\|b/100");
+        assert_eq!(markdown_cell("Intel(R) Xeon(R)\nCPU"), "Intel(R) Xeon(R) CPU");
+    }
+
+    fn run(results: &[(&str, usize, f64)]) -> RunRecord {
+        let results = results
+            .iter()
+            .map(|&(op_type, size, time_ns)| BenchmarkResult::new(format!("group/{}/{}", op_type, size), op_type.to_string(), size, time_ns))
+            .collect();
+        RunRecord::capture(results, None, None)
+    }
+
+    #[test]
+    fn trend_rows_line_up_runs_and_leave_gaps() {
+        let history = vec![
+            run(&[("Async", 100, 1_000.0), ("Sync", 100, 500.0)]),
+            run(&[("Async", 100, 1_500.0)]),
+            run(&[("Async", 100, 800.0), ("Sync", 100, 550.0)]),
+        ];
+        let rows = SimplePlotter::trend_rows(&history);
+
+        assert_eq!(rows[&("Async".to_string(), 100)], vec![Some(1_000.0), Some(1_500.0), Some(800.0)]);
+        assert_eq!(rows[&("Sync".to_string(), 100)], vec![Some(500.0), None, Some(550.0)]);
+    }
+
+    #[test]
+    fn trend_change_spans_first_to_last_value() {
+        assert_eq!(SimplePlotter::trend_change_pct(&[Some(1_000.0), Some(1_500.0), Some(750.0)]), Some(-25.0));
+        assert_eq!(SimplePlotter::trend_change_pct(&[None, Some(400.0), None, Some(500.0)]), Some(25.0));
+        assert_eq!(SimplePlotter::trend_change_pct(&[None, None]), None);
+    }
+}
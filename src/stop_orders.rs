@@ -0,0 +1,455 @@
+use crate::order_book::{BookError, OrderBook, PriceLevelKey, Trade};
+use crate::orders::{Order, OrderError, OrderSide, OrderStatus};
+use crate::price::Price;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+fn validate_stop(order: &Order) -> Result<Price, BookError> {
+    if !order.is_stop() {
+        return Err(BookError::UnsupportedOrderType(order.order_type));
+    }
+    order.stop_price.ok_or(BookError::Order(OrderError::MissingStopPrice))
+}
+
+/// A submit that stopped part way: `error` refused `rejected`, either the
+/// submitted order or a stop it triggered, after `trades` had already
+/// executed against the book.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmitError {
+    pub error: BookError,
+    pub rejected: Box<Order>,
+    pub trades: Vec<Trade>,
+}
+
+impl SubmitError {
+    fn new(error: BookError, mut rejected: Box<Order>, trades: Vec<Trade>) -> Self {
+        reject(&mut rejected);
+        SubmitError { error, rejected, trades }
+    }
+}
+
+// Marks a refused order rejected; one that has already traded or was
+// cancelled keeps its status
+fn reject(order: &mut Order) {
+    if order.status == OrderStatus::New {
+        order.reject().expect("new orders can be rejected");
+    }
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.trades.is_empty() {
+            write!(f, "{}", self.error)
+        } else {
+            write!(f, "{} (after {} trades)", self.error, self.trades.len())
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// What the task spawned by [`TriggerNotifier::spawn`] sends back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TriggerOutcome {
+    /// A stop fired, already converted to its market or limit form.
+    Triggered(Order),
+    /// A stop the task refused to park, e.g. for a duplicate order id.
+    Rejected { order: Order, error: BookError },
+}
+
+/// Parks `Stop` and `StopLimit` orders off-book and releases them once a
+/// trade crosses their stop price.
+///
+/// Buy stops are keyed by stop price so every level at or below the last
+/// trade fires with a single range scan; sell stops likewise for levels at
+/// or above it. Within a level orders fire in the order they were parked.
+#[derive(Debug, Clone, Default)]
+pub struct StopTriggers {
+    buy_stops: BTreeMap<Price, VecDeque<Order>>,
+    sell_stops: BTreeMap<Price, VecDeque<Order>>,
+    index: HashMap<u64, (OrderSide, Price)>,
+}
+
+impl StopTriggers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of parked stop orders.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn park(&mut self, order: Order) -> Result<(), BookError> {
+        self.try_park(order).map_err(|(_, error)| error)
+    }
+
+    // Like park, but hands a refused order back with the error
+    fn try_park(&mut self, order: Order) -> Result<(), (Box<Order>, BookError)> {
+        let stop_price = match validate_stop(&order) {
+            Ok(stop_price) => stop_price,
+            Err(error) => return Err((Box::new(order), error)),
+        };
+        if self.index.contains_key(&order.order_id) {
+            let error = BookError::DuplicateOrderId(order.order_id);
+            return Err((Box::new(order), error));
+        }
+
+        self.index.insert(order.order_id, (order.side, stop_price));
+        let levels = match order.side {
+            OrderSide::Buy => &mut self.buy_stops,
+            OrderSide::Sell => &mut self.sell_stops,
+        };
+        levels.entry(stop_price).or_default().push_back(order);
+        Ok(())
+    }
+
+    pub fn cancel(&mut self, order_id: u64) -> Result<Order, BookError> {
+        let (side, key) = self.index.remove(&order_id).ok_or(BookError::UnknownOrder(order_id))?;
+        let levels = match side {
+            OrderSide::Buy => &mut self.buy_stops,
+            OrderSide::Sell => &mut self.sell_stops,
+        };
+        let queue = levels.get_mut(&key).ok_or(BookError::UnknownOrder(order_id))?;
+        let position = queue
+            .iter()
+            .position(|order| order.order_id == order_id)
+            .ok_or(BookError::UnknownOrder(order_id))?;
+        let mut order = queue.remove(position).expect("position is in bounds");
+        if queue.is_empty() {
+            levels.remove(&key);
+        }
+        order.cancel()?;
+        Ok(order)
+    }
+
+    /// Releases every stop fired by a trade at `last_price`, already
+    /// converted to its market or limit form.
+    pub fn on_trade(&mut self, last_price: Price) -> Vec<Order> {
+        let mut triggered = self.fire(last_price);
+        for order in &mut triggered {
+            order.trigger();
+        }
+        triggered
+    }
+
+    /// Removes the stops fired by a trade at `last_price`, still in their
+    /// stop form so they can be parked again.
+    fn fire(&mut self, last: Price) -> Vec<Order> {
+        let mut fired_levels: Vec<Price> =
+            self.buy_stops.range(..=last).map(|(key, _)| *key).collect();
+        let sell_start = fired_levels.len();
+        fired_levels.extend(self.sell_stops.range(last..).rev().map(|(key, _)| *key));
+
+        let mut triggered = Vec::new();
+        for (i, key) in fired_levels.into_iter().enumerate() {
+            let levels = if i < sell_start { &mut self.buy_stops } else { &mut self.sell_stops };
+            if let Some(queue) = levels.remove(&key) {
+                for order in queue {
+                    self.index.remove(&order.order_id);
+                    triggered.push(order);
+                }
+            }
+        }
+        triggered
+    }
+
+    /// Submits an order to the book, parking it first if it is a stop, then
+    /// feeds every resulting trade back through the triggers until no more
+    /// stops fire. Returns all trades produced by the cascade.
+    ///
+    /// If the book rejects a triggered stop, the error returns it marked
+    /// rejected along with the trades executed so far, and the stops still
+    /// waiting in the cascade are parked again. A rejected `order` changes
+    /// nothing and comes back the same way, including one whose id is
+    /// already resting in `book` or parked here.
+    pub fn submit<K: PriceLevelKey>(&mut self, book: &mut OrderBook<K>, mut order: Order) -> Result<Vec<Trade>, SubmitError> {
+        // A parked stop sharing an id with a resting order would put two
+        // live orders with that id in the book once it fired
+        if self.index.contains_key(&order.order_id) || book.get_order(order.order_id).is_some() {
+            let error = BookError::DuplicateOrderId(order.order_id);
+            return Err(SubmitError::new(error, Box::new(order), Vec::new()));
+        }
+        if order.is_stop() {
+            if let Err(error) = validate_stop(&order) {
+                return Err(SubmitError::new(error, Box::new(order), Vec::new()));
+            }
+            // A stop whose price has already been crossed fires immediately
+            let fires_now = book
+                .last_trade_price()
+                .map(|last| order.is_triggered_by(last))
+                .unwrap_or(false);
+            if !fires_now {
+                return self
+                    .try_park(order)
+                    .map(|()| Vec::new())
+                    .map_err(|(order, error)| SubmitError::new(error, order, Vec::new()));
+            }
+            order.trigger();
+        }
+
+        // The book consumes the order, so keep a copy to hand back if it refuses
+        let mut trades = match book.add_order(order.clone()) {
+            Ok(trades) => trades,
+            Err(error) => return Err(SubmitError::new(error, Box::new(order), Vec::new())),
+        };
+        let mut pending: VecDeque<Order> = VecDeque::new();
+        for trade in &trades {
+            pending.extend(self.fire(trade.price));
+        }
+
+        while let Some(mut order) = pending.pop_front() {
+            order.trigger();
+            match book.add_order(order.clone()) {
+                Ok(new_trades) => {
+                    for trade in &new_trades {
+                        pending.extend(self.fire(trade.price));
+                    }
+                    trades.extend(new_trades);
+                }
+                Err(error) => {
+                    for stop in pending {
+                        self.park(stop).expect("fired stops were valid when parked");
+                    }
+                    return Err(SubmitError::new(error, Box::new(order), trades));
+                }
+            }
+        }
+
+        Ok(trades)
+    }
+}
+
+#[derive(Debug)]
+enum TriggerEvent {
+    Park(Order),
+    Trade(Price),
+}
+
+/// Event-driven counterpart to [`StopTriggers`]: stops and last-trade prices
+/// are sent to a background task, which pushes triggered orders out on a
+/// channel as soon as they fire. Stops the task cannot park, such as a
+/// duplicate id, come back on the same channel as rejected.
+#[derive(Debug, Clone)]
+pub struct TriggerNotifier {
+    events: mpsc::UnboundedSender<TriggerEvent>,
+}
+
+impl TriggerNotifier {
+    /// Spawns the trigger task on the current Tokio runtime. The task exits
+    /// once every notifier handle has been dropped, returning any stops that
+    /// never fired.
+    pub fn spawn() -> (Self, mpsc::UnboundedReceiver<TriggerOutcome>, JoinHandle<StopTriggers>) {
+        let (events, mut event_rx) = mpsc::unbounded_channel();
+        let (triggered_tx, triggered_rx) = mpsc::unbounded_channel();
+
+        let handle = tokio::spawn(async move {
+            let mut triggers = StopTriggers::new();
+            while let Some(event) = event_rx.recv().await {
+                match event {
+                    TriggerEvent::Park(order) => {
+                        // Type and stop price are validated by the sender, so
+                        // this is a duplicate id. Nobody is left to tell if
+                        // the receiver has gone.
+                        if let Err((mut order, error)) = triggers.try_park(order) {
+                            reject(&mut order);
+                            let _ = triggered_tx.send(TriggerOutcome::Rejected { order: *order, error });
+                        }
+                    }
+                    TriggerEvent::Trade(price) => {
+                        for order in triggers.on_trade(price) {
+                            if triggered_tx.send(TriggerOutcome::Triggered(order)).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            triggers
+        });
+
+        (Self { events }, triggered_rx, handle)
+    }
+
+    pub fn park(&self, order: Order) -> Result<(), BookError> {
+        validate_stop(&order)?;
+        self.events
+            .send(TriggerEvent::Park(order))
+            .map_err(|_| BookError::TriggerEngineClosed)
+    }
+
+    pub fn publish_trade(&self, price: Price) -> Result<(), BookError> {
+        self.events
+            .send(TriggerEvent::Trade(price))
+            .map_err(|_| BookError::TriggerEngineClosed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order_book::TickOrderBook;
+    use crate::orders::OrderType;
+    use crate::price::{PriceError, SymbolSpec};
+
+    fn px(value: f64) -> Price {
+        Price::from_f64(value).unwrap()
+    }
+
+    fn order(order_id: u64, side: OrderSide, order_type: OrderType, quantity: u64, price: f64) -> Order {
+        Order::with_details(order_id, "AAPL".to_string(), "CLIENT_1".to_string(), side, order_type, quantity, px(price), None, order_id)
+            .unwrap()
+    }
+
+    fn stop(order_id: u64, order_type: OrderType, quantity: u64, price: f64, stop_price: f64) -> Order {
+        Order::with_details(order_id, "AAPL".to_string(), "CLIENT_1".to_string(), OrderSide::Buy, order_type, quantity, px(price), Some(px(stop_price)), order_id)
+            .unwrap()
+    }
+
+    fn book_with_asks(prices: &[f64]) -> TickOrderBook {
+        let mut book = TickOrderBook::with_spec("AAPL".to_string(), SymbolSpec::default());
+        for (i, &price) in prices.iter().enumerate() {
+            book.add_order(order(i as u64 + 1, OrderSide::Sell, OrderType::Limit, 10, price)).unwrap();
+        }
+        book
+    }
+
+    #[test]
+    fn trades_cascade_through_stops() {
+        let mut book = book_with_asks(&[100.0, 101.0, 102.0]);
+        let mut triggers = StopTriggers::new();
+        triggers.submit(&mut book, stop(10, OrderType::Stop, 10, 0.0, 100.0)).unwrap();
+        triggers.submit(&mut book, stop(11, OrderType::Stop, 10, 0.0, 101.0)).unwrap();
+        assert_eq!(triggers.len(), 2);
+
+        let trades = triggers.submit(&mut book, order(20, OrderSide::Buy, OrderType::Limit, 10, 100.0)).unwrap();
+
+        let fills: Vec<(u64, u64, f64)> = trades
+            .iter()
+            .map(|trade| (trade.taker_order_id, trade.maker_order_id, trade.price.to_f64()))
+            .collect();
+        assert_eq!(fills, vec![(20, 1, 100.0), (10, 2, 101.0), (11, 3, 102.0)]);
+        assert!(triggers.is_empty());
+        assert!(book.is_empty());
+    }
+
+    #[test]
+    fn crossed_stop_fires_on_submit() {
+        let mut book = book_with_asks(&[100.0, 101.0]);
+        let mut triggers = StopTriggers::new();
+        triggers.submit(&mut book, order(20, OrderSide::Buy, OrderType::Limit, 10, 100.0)).unwrap();
+
+        let trades = triggers.submit(&mut book, stop(10, OrderType::StopLimit, 5, 101.0, 99.0)).unwrap();
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].price, px(101.0));
+        assert!(triggers.is_empty());
+    }
+
+    #[test]
+    fn rejected_stop_keeps_trades_and_reparks_the_rest() {
+        let mut book = book_with_asks(&[100.0, 101.0]);
+        let mut triggers = StopTriggers::new();
+        // Fires first at 100.00 and is rejected: its limit is off the cent tick
+        triggers.submit(&mut book, stop(10, OrderType::StopLimit, 10, 101.005, 100.0)).unwrap();
+        triggers.submit(&mut book, stop(11, OrderType::Stop, 10, 0.0, 100.0)).unwrap();
+
+        let err = triggers
+            .submit(&mut book, order(20, OrderSide::Buy, OrderType::Limit, 10, 100.0))
+            .unwrap_err();
+
+        assert!(matches!(err.error, BookError::Price(PriceError::NotOnTick { .. })));
+        assert_eq!(err.rejected.order_id, 10);
+        assert_eq!(err.rejected.order_type, OrderType::Limit);
+        assert_eq!(err.rejected.status, OrderStatus::Rejected);
+        assert_eq!(err.trades.len(), 1);
+        assert_eq!(err.trades[0].maker_order_id, 1);
+        assert_eq!(triggers.len(), 1);
+        let reparked = triggers.cancel(11).unwrap();
+        assert_eq!(reparked.order_type, OrderType::Stop);
+        assert_eq!(book.depth_at(OrderSide::Sell, px(101.0)), 10);
+    }
+
+    #[test]
+    fn rejected_order_changes_nothing() {
+        let mut book = book_with_asks(&[100.0]);
+        let mut triggers = StopTriggers::new();
+        triggers.submit(&mut book, stop(10, OrderType::Stop, 10, 0.0, 100.0)).unwrap();
+
+        let err = triggers
+            .submit(&mut book, order(1, OrderSide::Buy, OrderType::Limit, 10, 100.0))
+            .unwrap_err();
+
+        assert_eq!(err.error, BookError::DuplicateOrderId(1));
+        assert_eq!(err.rejected.order_id, 1);
+        assert_eq!(err.rejected.status, OrderStatus::Rejected);
+        assert!(err.trades.is_empty());
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(book.depth_at(OrderSide::Sell, px(100.0)), 10);
+    }
+
+    #[test]
+    fn duplicate_stop_is_returned() {
+        let mut book = book_with_asks(&[100.0]);
+        let mut triggers = StopTriggers::new();
+        triggers.submit(&mut book, stop(10, OrderType::Stop, 10, 0.0, 101.0)).unwrap();
+
+        let err = triggers.submit(&mut book, stop(10, OrderType::Stop, 5, 0.0, 102.0)).unwrap_err();
+
+        assert_eq!(err.error, BookError::DuplicateOrderId(10));
+        assert_eq!(err.rejected.quantity, 5);
+        assert_eq!(err.rejected.status, OrderStatus::Rejected);
+        assert_eq!(triggers.len(), 1);
+    }
+
+    #[test]
+    fn ids_are_unique_across_book_and_stops() {
+        // Ask 1 rests in the book, so a stop may not take its id
+        let mut book = book_with_asks(&[100.0, 101.0]);
+        let mut triggers = StopTriggers::new();
+        let err = triggers.submit(&mut book, stop(1, OrderType::Stop, 5, 0.0, 100.0)).unwrap_err();
+
+        assert_eq!(err.error, BookError::DuplicateOrderId(1));
+        assert_eq!(err.rejected.status, OrderStatus::Rejected);
+        assert!(triggers.is_empty());
+
+        // Nor may a stop that would fire straight away
+        triggers.submit(&mut book, order(20, OrderSide::Buy, OrderType::Limit, 5, 100.0)).unwrap();
+        let err = triggers.submit(&mut book, stop(1, OrderType::Stop, 5, 0.0, 99.0)).unwrap_err();
+        assert_eq!(err.error, BookError::DuplicateOrderId(1));
+        assert_eq!(book.get_order(1).map(|order| order.remaining_quantity), Some(5));
+    }
+
+    #[tokio::test]
+    async fn notifier_reports_duplicate_stops() {
+        let (notifier, mut outcomes, handle) = TriggerNotifier::spawn();
+        notifier.park(stop(10, OrderType::Stop, 10, 0.0, 101.0)).unwrap();
+        notifier.park(stop(10, OrderType::Stop, 5, 0.0, 102.0)).unwrap();
+        notifier.publish_trade(px(101.0)).unwrap();
+        drop(notifier);
+
+        match outcomes.recv().await {
+            Some(TriggerOutcome::Rejected { order, error }) => {
+                assert_eq!(error, BookError::DuplicateOrderId(10));
+                assert_eq!(order.quantity, 5);
+                assert_eq!(order.status, OrderStatus::Rejected);
+            }
+            other => panic!("expected a rejected stop, got {:?}", other),
+        }
+        match outcomes.recv().await {
+            Some(TriggerOutcome::Triggered(order)) => {
+                assert_eq!(order.quantity, 10);
+                assert_eq!(order.order_type, OrderType::Market);
+            }
+            other => panic!("expected a triggered stop, got {:?}", other),
+        }
+        assert_eq!(outcomes.recv().await, None);
+        assert!(handle.await.unwrap().is_empty());
+    }
+}
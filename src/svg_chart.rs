@@ -0,0 +1,376 @@
+use std::fmt::Write;
+
+const WIDTH: f64 = 820.0;
+const HEIGHT: f64 = 480.0;
+const MARGIN_LEFT: f64 = 80.0;
+const MARGIN_RIGHT: f64 = 230.0;
+const MARGIN_TOP: f64 = 44.0;
+const MARGIN_BOTTOM: f64 = 56.0;
+
+/// Series colours, reused in order when there are more series than colours.
+const PALETTE: [&str; 8] = [
+    "#d62728", "#1f77b4", "#2ca02c", "#9467bd", "#17becf", "#ff7f0e", "#8c564b", "#e377c2",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Linear,
+    /// Base-10 logarithmic; non-positive values are clamped to the axis minimum.
+    Log,
+}
+
+/// One measurement with its error bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub low: f64,
+    pub high: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64, low: f64, high: f64) -> Self {
+        Self { x, y, low, high }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Series {
+    pub label: String,
+    pub points: Vec<Point>,
+}
+
+/// Line chart with error bars rendered to a standalone `<svg>` element.
+///
+/// Only SVG markup is produced; text is laid out by whatever displays it, so
+/// no fonts or system libraries are needed to render.
+#[derive(Debug, Clone)]
+pub struct SvgChart {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    pub x_scale: Scale,
+    pub y_scale: Scale,
+    pub series: Vec<Series>,
+}
+
+struct Axis {
+    scale: Scale,
+    min: f64,
+    max: f64,
+    start: f64,
+    end: f64,
+}
+
+impl Axis {
+    fn fit(scale: Scale, values: impl Iterator<Item = f64>, start: f64, end: f64) -> Self {
+        let values: Vec<f64> = values
+            .filter(|value| value.is_finite() && (scale == Scale::Linear || *value > 0.0))
+            .collect();
+        let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        let (min, max) = match scale {
+            Scale::Log if values.is_empty() => (1.0, 10.0),
+            Scale::Log => {
+                let min = 10f64.powf(low.log10().floor());
+                let max = 10f64.powf(high.log10().ceil());
+                (min, if max > min { max } else { min * 10.0 })
+            }
+            Scale::Linear if values.is_empty() => (0.0, 1.0),
+            Scale::Linear => {
+                let min = low.min(0.0);
+                let step = nice_step((high - min) / 5.0);
+                let max = (high / step).ceil() * step;
+                (min, if max > min { max } else { min + step })
+            }
+        };
+        Self { scale, min, max, start, end }
+    }
+
+    fn position(&self, value: f64) -> f64 {
+        let fraction = match self.scale {
+            Scale::Linear => (value - self.min) / (self.max - self.min),
+            Scale::Log => {
+                let value = value.max(self.min);
+                (value.log10() - self.min.log10()) / (self.max.log10() - self.min.log10())
+            }
+        };
+        self.start + fraction * (self.end - self.start)
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        match self.scale {
+            Scale::Log => {
+                let first = self.min.log10().round() as i32;
+                let last = self.max.log10().round() as i32;
+                (first..=last).map(|exponent| 10f64.powi(exponent)).collect()
+            }
+            Scale::Linear => {
+                let step = nice_step((self.max - self.min) / 5.0);
+                let count = ((self.max - self.min) / step).round() as usize;
+                (0..=count).map(|i| self.min + step * i as f64).collect()
+            }
+        }
+    }
+}
+
+// 1, 2 or 5 times a power of ten, at least `raw`.
+fn nice_step(raw: f64) -> f64 {
+    if !(raw.is_finite() && raw > 0.0) {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let residual = raw / magnitude;
+    let factor = if residual <= 1.0 {
+        1.0
+    } else if residual <= 2.0 {
+        2.0
+    } else if residual <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    factor * magnitude
+}
+
+fn format_tick(value: f64) -> String {
+    let (scaled, suffix) = if value.abs() >= 1e9 {
+        (value / 1e9, "G")
+    } else if value.abs() >= 1e6 {
+        (value / 1e6, "M")
+    } else if value.abs() >= 1e3 {
+        (value / 1e3, "k")
+    } else {
+        (value, "")
+    };
+    let text = format!("{:.3}", scaled);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    format!("{}{}", text, suffix)
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl SvgChart {
+    pub fn new(title: &str, x_label: &str, y_label: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            x_label: x_label.to_string(),
+            y_label: y_label.to_string(),
+            x_scale: Scale::Log,
+            y_scale: Scale::Log,
+            series: Vec::new(),
+        }
+    }
+
+    pub fn with_scales(mut self, x_scale: Scale, y_scale: Scale) -> Self {
+        self.x_scale = x_scale;
+        self.y_scale = y_scale;
+        self
+    }
+
+    pub fn add_series(&mut self, label: &str, mut points: Vec<Point>) {
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+        self.series.push(Series {
+            label: label.to_string(),
+            points,
+        });
+    }
+
+    pub fn to_svg(&self) -> String {
+        let points = || self.series.iter().flat_map(|series| series.points.iter());
+        let x_axis = Axis::fit(self.x_scale, points().map(|p| p.x), MARGIN_LEFT, WIDTH - MARGIN_RIGHT);
+        let y_axis = Axis::fit(
+            self.y_scale,
+            points().flat_map(|p| [p.y, p.low, p.high]),
+            HEIGHT - MARGIN_BOTTOM,
+            MARGIN_TOP,
+        );
+        let plot_right = WIDTH - MARGIN_RIGHT;
+        let plot_bottom = HEIGHT - MARGIN_BOTTOM;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {h}" width="{w}" height="{h}" font-family="sans-serif" font-size="12" role="img">"#,
+            w = WIDTH,
+            h = HEIGHT
+        );
+        let _ = writeln!(svg, "<title>{}</title>", escape_xml(&self.title));
+        let _ = writeln!(svg, r##"<rect width="100%" height="100%" fill="#ffffff"/>"##);
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="24" text-anchor="middle" font-size="16" font-weight="bold">{}</text>"#,
+            (MARGIN_LEFT + plot_right) / 2.0,
+            escape_xml(&self.title)
+        );
+
+        // Grid and tick labels
+        for tick in x_axis.ticks() {
+            let x = x_axis.position(tick);
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="{bottom}" stroke="#e0e0e0"/><text x="{x:.1}" y="{label}" text-anchor="middle">{text}</text>"##,
+                top = MARGIN_TOP,
+                bottom = plot_bottom,
+                label = plot_bottom + 16.0,
+                text = format_tick(tick)
+            );
+        }
+        for tick in y_axis.ticks() {
+            let y = y_axis.position(tick);
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{left}" y1="{y:.1}" x2="{right}" y2="{y:.1}" stroke="#e0e0e0"/><text x="{label}" y="{text_y:.1}" text-anchor="end">{text}</text>"##,
+                left = MARGIN_LEFT,
+                right = plot_right,
+                label = MARGIN_LEFT - 6.0,
+                text_y = y + 4.0,
+                text = format_tick(tick)
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#333333"/>"##,
+            MARGIN_LEFT,
+            MARGIN_TOP,
+            plot_right - MARGIN_LEFT,
+            plot_bottom - MARGIN_TOP
+        );
+
+        // Axis labels
+        let scale_note = |scale: Scale| if scale == Scale::Log { " (log)" } else { "" };
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}{}</text>"#,
+            (MARGIN_LEFT + plot_right) / 2.0,
+            HEIGHT - 14.0,
+            escape_xml(&self.x_label),
+            scale_note(self.x_scale)
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text transform="translate(18 {}) rotate(-90)" text-anchor="middle">{}{}</text>"#,
+            (MARGIN_TOP + plot_bottom) / 2.0,
+            escape_xml(&self.y_label),
+            scale_note(self.y_scale)
+        );
+
+        // Series: line, error bars, markers and a legend entry
+        for (index, series) in self.series.iter().enumerate() {
+            let color = PALETTE[index % PALETTE.len()];
+            let _ = writeln!(svg, r#"<g class="series" data-series="{}">"#, escape_xml(&series.label));
+
+            let path: Vec<String> = series
+                .points
+                .iter()
+                .map(|p| format!("{:.1},{:.1}", x_axis.position(p.x), y_axis.position(p.y)))
+                .collect();
+            let _ = writeln!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                path.join(" "),
+                color
+            );
+
+            for point in &series.points {
+                let x = x_axis.position(point.x);
+                let y = y_axis.position(point.y);
+                let low = y_axis.position(point.low);
+                let high = y_axis.position(point.high);
+                let _ = writeln!(
+                    svg,
+                    r#"<path d="M{x:.1},{low:.1}V{high:.1}M{l:.1},{low:.1}H{r:.1}M{l:.1},{high:.1}H{r:.1}" stroke="{color}" fill="none"/>"#,
+                    l = x - 4.0,
+                    r = x + 4.0
+                );
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{x:.1}" cy="{y:.1}" r="4" fill="{color}"><title>{label}: {px} → {py} ({plow}–{phigh})</title></circle>"#,
+                    label = escape_xml(&series.label),
+                    px = format_tick(point.x),
+                    py = format_tick(point.y),
+                    plow = format_tick(point.low),
+                    phigh = format_tick(point.high)
+                );
+            }
+
+            let legend_y = MARGIN_TOP + 8.0 + index as f64 * 20.0;
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="14" height="4" fill="{}"/><text x="{}" y="{}">{}</text>"#,
+                plot_right + 16.0,
+                legend_y - 2.0,
+                color,
+                plot_right + 36.0,
+                legend_y + 4.0,
+                escape_xml(&series.label)
+            );
+            let _ = writeln!(svg, "</g>");
+        }
+
+        if self.series.iter().all(|series| series.points.is_empty()) {
+            let _ = writeln!(
+                svg,
+                r##"<text x="{}" y="{}" text-anchor="middle" fill="#888888">No data</text>"##,
+                (MARGIN_LEFT + plot_right) / 2.0,
+                (MARGIN_TOP + plot_bottom) / 2.0
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_axis_clamps_non_positive_bounds() {
+        let mut chart = SvgChart::new("Latency", "Orders", "Time (ns)");
+        chart.add_series("Async", vec![Point::new(100.0, 500.0, 0.0, 900.0), Point::new(1000.0, 4_000.0, -50.0, 6_000.0)]);
+        let svg = chart.to_svg();
+
+        assert!(!svg.contains("NaN") && !svg.contains("inf"), "{}", svg);
+        // Both lower bounds sit on the bottom of the plot
+        let bottom = format!(",{:.1}V", HEIGHT - MARGIN_BOTTOM);
+        assert_eq!(svg.matches(&bottom).count(), 2, "{}", svg);
+
+        let axis = Axis::fit(Scale::Log, [500.0, 0.0, -50.0, 6_000.0].into_iter(), 0.0, 100.0);
+        assert_eq!((axis.min, axis.max), (100.0, 10_000.0));
+        assert_eq!(axis.position(0.0), axis.position(axis.min));
+    }
+
+    #[test]
+    fn empty_chart_says_no_data() {
+        let mut chart = SvgChart::new("Latency", "Orders", "Time (ns)");
+        assert!(chart.to_svg().contains(">No data</text>"));
+
+        chart.add_series("Async", Vec::new());
+        let svg = chart.to_svg();
+        assert!(svg.contains(">No data</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+
+        chart.add_series("Sync", vec![Point::new(100.0, 50.0, 40.0, 60.0)]);
+        assert!(!chart.to_svg().contains("No data"));
+    }
+
+    #[test]
+    fn labels_are_escaped() {
+        let mut chart = SvgChart::new("Sync <HashMap> & \"DashMap\"", "Orders", "Time");
+        chart.add_series("Async <JoinSet>", vec![Point::new(10.0, 1.0, 1.0, 1.0)]);
+        let svg = chart.to_svg();
+
+        assert!(svg.contains("<title>Sync &lt;HashMap&gt; &amp; &quot;DashMap&quot;</title>"), "{}", svg);
+        assert!(svg.contains(r#"data-series="Async &lt;JoinSet&gt;""#));
+        assert!(!svg.contains("<JoinSet>") && !svg.contains("<HashMap>"));
+        assert_eq!(escape_xml("a&b<c>\"d\""), "a&amp;b&lt;c&gt;&quot;d&quot;");
+    }
+}
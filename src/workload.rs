@@ -0,0 +1,227 @@
+use crate::interner::SymbolTable;
+use crate::orders::{CompactOrder, Order, OrderSide, OrderType};
+use crate::price::Price;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::fs;
+use std::path::Path;
+
+/// Seed used when `HFT_BENCH_SEED` is not set.
+pub const DEFAULT_SEED: u64 = 0x5EED_0000_4F52_4453;
+
+/// Environment variable that overrides the workload seed.
+pub const SEED_ENV_VAR: &str = "HFT_BENCH_SEED";
+
+/// File written next to the Criterion output recording the seed of the run.
+pub const SEED_FILE_NAME: &str = "workload_seed";
+
+/// Seeded source of orders and fills for the benchmarks.
+///
+/// Two generators built from the same seed produce the same stream, so the
+/// sync, DashMap and async variants of a scenario process identical input.
+#[derive(Debug, Clone)]
+pub struct WorkloadGenerator {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl WorkloadGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Independent generator for one of many streams drawn from `seed`, so
+    /// work split across tasks can generate its own input in any order.
+    pub fn for_stream(seed: u64, stream: u64) -> Self {
+        Self::new(seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    /// Reads the seed from `HFT_BENCH_SEED`, falling back to [`DEFAULT_SEED`].
+    pub fn from_env() -> Result<Self, String> {
+        seed_from_env().map(Self::new)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Direct access to the underlying RNG for scenario-specific draws.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Random order with the same distribution as [`Order::new`].
+    pub fn next_order(&mut self, order_id: u64, symbol: String, client_id: String) -> Order {
+        Order::random(&mut self.rng, order_id, symbol, client_id)
+    }
+
+    /// Quantity to fill against an order with `remaining` open quantity.
+    pub fn next_fill(&mut self, remaining: u64) -> u64 {
+        if remaining == 0 {
+            0
+        } else {
+            self.rng.gen_range(1..=remaining)
+        }
+    }
+
+    /// Limit or market order priced in cent steps around 100.00, so both
+    /// sides of an order book cross regularly.
+    pub fn next_book_order(&mut self, order_id: u64, symbol: String, client_id: String) -> Order {
+        let rng = &mut self.rng;
+        let side = if rng.gen_bool(0.5) { OrderSide::Buy } else { OrderSide::Sell };
+        let order_type = if rng.gen_bool(0.1) { OrderType::Market } else { OrderType::Limit };
+        let price = Price::from_cents(rng.gen_range(9_900..10_100));
+        Order::with_details(
+            order_id,
+            symbol,
+            client_id,
+            side,
+            order_type,
+            rng.gen_range(1..1000),
+            price,
+            None,
+            rng.gen_range(1600000000..1700000000),
+        )
+        .expect("generated order is valid")
+    }
+
+    /// Stop or stop-limit order with a stop price between 95.00 and 105.00.
+    pub fn next_stop_order(&mut self, order_id: u64, symbol: String, client_id: String) -> Order {
+        let rng = &mut self.rng;
+        let side = if rng.gen_bool(0.5) { OrderSide::Buy } else { OrderSide::Sell };
+        let order_type = if rng.gen_bool(0.5) { OrderType::Stop } else { OrderType::StopLimit };
+        let stop_price = Price::from_cents(rng.gen_range(9_500..10_500));
+        Order::with_details(
+            order_id,
+            symbol,
+            client_id,
+            side,
+            order_type,
+            rng.gen_range(1..1000),
+            stop_price,
+            Some(stop_price),
+            rng.gen_range(1600000000..1700000000),
+        )
+        .expect("generated stop order is valid")
+    }
+
+    /// Random walk of last-trade prices around 100.00, in cent steps.
+    pub fn trade_prices(&mut self, count: usize) -> Vec<Price> {
+        let mut cents: i64 = 10_000;
+        (0..count)
+            .map(|_| {
+                cents = (cents + self.rng.gen_range(-25..=25)).clamp(9_400, 10_600);
+                Price::from_cents(cents)
+            })
+            .collect()
+    }
+}
+
+/// Pre-generated order stream with one fill per order, shared by every
+/// variant of the basic order operation benchmarks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderWorkload {
+    pub seed: u64,
+    pub orders: Vec<Order>,
+    pub fills: Vec<u64>,
+}
+
+impl OrderWorkload {
+    /// Orders cycle through `symbols` symbols and `clients` clients using the
+    /// same `STOCK_n` / `CLIENT_n` naming as the benchmarks.
+    pub fn generate(seed: u64, data_size: usize, symbols: usize, clients: usize) -> Self {
+        let mut generator = WorkloadGenerator::new(seed);
+        let orders: Vec<Order> = (0..data_size)
+            .map(|i| {
+                generator.next_order(
+                    i as u64,
+                    format!("STOCK_{}", i % symbols),
+                    format!("CLIENT_{}", i % clients),
+                )
+            })
+            .collect();
+        let fills = orders
+            .iter()
+            .map(|order| generator.next_fill(order.remaining_quantity))
+            .collect();
+
+        Self { seed, orders, fills }
+    }
+
+    /// The same orders with symbols and clients interned into `table`.
+    pub fn compact_orders(&self, table: &mut SymbolTable) -> Vec<CompactOrder> {
+        self.orders
+            .iter()
+            .map(|order| CompactOrder::from_order(order, table))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+}
+
+/// The seed in `HFT_BENCH_SEED`, or [`DEFAULT_SEED`] when it is not set.
+/// A value that is not an unsigned integer is an error rather than quietly
+/// replaced, since the run would not be the one asked for.
+pub fn seed_from_env() -> Result<u64, String> {
+    let Ok(value) = std::env::var(SEED_ENV_VAR) else {
+        return Ok(DEFAULT_SEED);
+    };
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} must be an unsigned integer, got '{}'", SEED_ENV_VAR, value))
+}
+
+/// Records the seed of a benchmark run in `dir` so reporting tools can pick
+/// it up later.
+pub fn record_seed(dir: &Path, seed: u64) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(SEED_FILE_NAME), seed.to_string())
+}
+
+pub fn load_recorded_seed(dir: &Path) -> Option<u64> {
+    fs::read_to_string(dir.join(SEED_FILE_NAME))
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_workload() {
+        let workload = OrderWorkload::generate(42, 500, 10, 5);
+
+        assert_eq!(workload, OrderWorkload::generate(42, 500, 10, 5));
+        assert_eq!(workload.len(), 500);
+        assert_eq!(workload.fills.len(), 500);
+    }
+
+    #[test]
+    fn different_seeds_differ() {
+        let workload = OrderWorkload::generate(42, 500, 10, 5);
+        let other = OrderWorkload::generate(43, 500, 10, 5);
+
+        assert_ne!(workload.orders, other.orders);
+        assert_ne!(workload.fills, other.fills);
+    }
+
+    #[test]
+    fn streams_are_independent_of_order() {
+        let first = WorkloadGenerator::for_stream(42, 1).next_order(1, "S".to_string(), "C".to_string());
+        let _ = WorkloadGenerator::for_stream(42, 2).next_order(2, "S".to_string(), "C".to_string());
+
+        assert_eq!(first, WorkloadGenerator::for_stream(42, 1).next_order(1, "S".to_string(), "C".to_string()));
+        assert_ne!(first, WorkloadGenerator::for_stream(42, 2).next_order(1, "S".to_string(), "C".to_string()));
+    }
+}